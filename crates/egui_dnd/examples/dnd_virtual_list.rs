@@ -1,7 +1,7 @@
 use eframe::epaint::Margin;
 use eframe::{egui, NativeOptions};
 use egui::{CentralPanel, Frame, Id, ScrollArea};
-use egui_dnd::dnd;
+use egui_dnd::{dnd, DragDropConfig};
 use egui_virtual_list::VirtualList;
 use rand::prelude::StdRng;
 use rand::{RngExt, SeedableRng};
@@ -16,25 +16,37 @@ pub fn main() -> eframe::Result<()> {
         move |ui, _frame| {
             CentralPanel::default().show(ui, |ui| {
                 ScrollArea::vertical().show(ui, |ui| {
-                    let response = dnd(ui, "dnd").show_custom(|ui, iter| {
-                        virtual_list.ui_custom_layout(ui, items.len(), |ui, start_index| {
-                            let item = &items[start_index];
+                    // Drag an item to the top or bottom edge to scroll the list. Only the visible
+                    // part of the scroll area is considered, so this works with virtual lists too.
+                    // The list is long, so we scroll a bit faster than the default.
+                    let response = dnd(ui, "dnd")
+                        .with_mouse_config(DragDropConfig {
+                            auto_scroll_speed: 2000.0,
+                            ..DragDropConfig::mouse()
+                        })
+                        .with_touch_config(Some(DragDropConfig {
+                            auto_scroll_speed: 2000.0,
+                            ..DragDropConfig::touch_scroll()
+                        }))
+                        .show_custom(|ui, iter| {
+                            virtual_list.ui_custom_layout(ui, items.len(), |ui, start_index| {
+                                let item = &items[start_index];
 
-                            iter.next(
-                                ui,
-                                Id::new(*item), // assumes that each item is a unique hash
-                                start_index,
-                                true,
-                                |ui, dnd_item| {
-                                    dnd_item.ui(ui, |ui, handle, _item_state| {
-                                        draw_item(ui, handle, *item);
-                                    })
-                                },
-                            );
+                                iter.next(
+                                    ui,
+                                    Id::new(*item), // assumes that each item is a unique hash
+                                    start_index,
+                                    true,
+                                    |ui, dnd_item| {
+                                        dnd_item.ui(ui, |ui, handle, _item_state| {
+                                            draw_item(ui, handle, *item);
+                                        })
+                                    },
+                                );
 
-                            1
+                                1
+                            });
                         });
-                    });
 
                     // Use update_vec() (or update your data structure yourself on every frame) for
                     // smooth dragging and dropping, as updating based on response.final_update()
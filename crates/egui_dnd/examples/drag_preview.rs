@@ -0,0 +1,67 @@
+use eframe::{egui, NativeOptions};
+use egui::{CentralPanel, Frame, Id, Sense};
+use egui_dnd::{dnd, InsertionIndicator};
+use std::collections::HashSet;
+
+pub fn main() -> eframe::Result<()> {
+    let mut items: Vec<String> = (0..10).map(|i| format!("item {i}")).collect();
+    let mut selection: HashSet<Id> = HashSet::new();
+    let mut indicator = InsertionIndicator::Line;
+    let mut placeholder = true;
+
+    eframe::run_ui_native(
+        "DnD Drag Preview Example",
+        NativeOptions::default(),
+        move |ui, _frame| {
+            CentralPanel::default().show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.selectable_value(&mut indicator, InsertionIndicator::Gap, "Gap");
+                    ui.selectable_value(&mut indicator, InsertionIndicator::Line, "Line");
+                    ui.selectable_value(&mut indicator, InsertionIndicator::None, "None");
+                    ui.checkbox(&mut placeholder, "Placeholder");
+                });
+                ui.label("Ctrl/Cmd-click to select multiple items.");
+
+                let mut clicked = None;
+                dnd(ui, "dnd_drag_preview_example")
+                    .with_insertion_indicator(indicator)
+                    .with_placeholder(placeholder)
+                    .with_selection(selection.iter().copied())
+                    .with_drag_preview(|ui, preview| {
+                        // A semi-transparent card instead of the item itself
+                        ui.multiply_opacity(0.7);
+                        Frame::group(ui.style())
+                            .fill(ui.visuals().extreme_bg_color)
+                            .show(ui, |ui| {
+                                ui.set_min_size(ui.available_size());
+                                if preview.count > 1 {
+                                    ui.strong(format!("{} items", preview.count));
+                                } else {
+                                    ui.strong(format!("Moving item #{}", preview.index + 1));
+                                }
+                            });
+                    })
+                    .show_vec(&mut items, |ui, item, handle, _state| {
+                        let id = Id::new(&*item);
+                        let response = handle.sense(Sense::click()).ui(ui, |ui| {
+                            ui.selectable_label(selection.contains(&id), item.as_str());
+                        });
+                        if response.clicked() {
+                            clicked = Some(id);
+                        }
+                    });
+
+                if let Some(id) = clicked {
+                    if ui.input(|i| i.modifiers.command) {
+                        if !selection.remove(&id) {
+                            selection.insert(id);
+                        }
+                    } else {
+                        selection.clear();
+                        selection.insert(id);
+                    }
+                }
+            });
+        },
+    )
+}
@@ -0,0 +1,52 @@
+use eframe::{egui, NativeOptions};
+use egui::{CentralPanel, Frame, Id};
+use egui_dnd::{dnd, DropTarget};
+
+pub fn main() -> eframe::Result<()> {
+    let mut items: Vec<String> = (0..8).map(|i| format!("item {i}")).collect();
+    let mut archive: Vec<String> = Vec::new();
+
+    eframe::run_ui_native(
+        "DnD Drop Target Example",
+        NativeOptions::default(),
+        move |ui, _frame| {
+            CentralPanel::default().show(ui, |ui| {
+                let response = dnd(ui, "dnd_drop_target_example").show_vec(
+                    &mut items,
+                    |ui, item, handle, _state| {
+                        handle.ui(ui, |ui| {
+                            ui.label(item.as_str());
+                        });
+                    },
+                );
+
+                ui.separator();
+
+                ui.horizontal(|ui| {
+                    DropTarget::new("dnd_drop_target_example", "trash").show(ui, |ui, hovered| {
+                        Frame::group(ui.style()).show(ui, |ui| {
+                            ui.label(if hovered {
+                                "🗑 Release to delete"
+                            } else {
+                                "🗑 Trash"
+                            });
+                        });
+                    });
+
+                    DropTarget::new("dnd_drop_target_example", "archive").show(ui, |ui, _| {
+                        Frame::group(ui.style()).show(ui, |ui| {
+                            ui.label(format!("🗄 Archive ({})", archive.len()));
+                        });
+                    });
+                });
+
+                if let Some(drop) = response.final_external_drop() {
+                    let item = items.remove(drop.from);
+                    if drop.target == Id::new("archive") {
+                        archive.push(item);
+                    }
+                }
+            });
+        },
+    )
+}
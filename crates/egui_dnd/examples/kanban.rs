@@ -0,0 +1,56 @@
+use eframe::{egui, NativeOptions};
+use egui::{CentralPanel, Id};
+use egui_dnd::dnd;
+use egui_dnd::utils::move_between_vecs;
+
+pub fn main() -> eframe::Result<()> {
+    let mut columns = vec![
+        ("todo", vec!["write docs", "fix bug", "review pr"]),
+        ("doing", vec!["refactor", "release"]),
+        ("done", vec!["setup ci"]),
+    ];
+
+    eframe::run_ui_native(
+        "DnD Kanban Example",
+        NativeOptions::default(),
+        move |ui, _frame| {
+            CentralPanel::default().show(ui, |ui| {
+                let mut cross_list_update = None;
+
+                ui.columns(columns.len(), |uis| {
+                    for (ui, (name, items)) in uis.iter_mut().zip(columns.iter_mut()) {
+                        let name = *name;
+                        ui.heading(name);
+                        let response = dnd(ui, name)
+                            .with_group("kanban")
+                            // Nothing can be moved back to todo once it's done
+                            .with_accept(move |list| name != "todo" || list != Id::new("done"))
+                            .show_vec(items, |ui, item, handle, _state| {
+                                handle.ui(ui, |ui| {
+                                    ui.label(*item);
+                                });
+                            });
+
+                        if let Some(update) = response.final_cross_list_update() {
+                            cross_list_update = Some(update);
+                        }
+                    }
+                });
+
+                if let Some(update) = cross_list_update {
+                    let from = columns
+                        .iter()
+                        .position(|(name, _)| Id::new(*name) == update.from_list);
+                    let to = columns
+                        .iter()
+                        .position(|(name, _)| Id::new(*name) == update.to_list);
+                    if let (Some(from), Some(to)) = (from, to) {
+                        let mut source = std::mem::take(&mut columns[from].1);
+                        move_between_vecs(update.from, update.to, &mut source, &mut columns[to].1);
+                        columns[from].1 = source;
+                    }
+                }
+            });
+        },
+    )
+}
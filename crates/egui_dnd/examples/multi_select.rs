@@ -0,0 +1,55 @@
+use eframe::{egui, NativeOptions};
+use egui::{CentralPanel, Id, Sense};
+use egui_dnd::dnd;
+use std::collections::HashSet;
+
+pub fn main() -> eframe::Result<()> {
+    let mut items: Vec<String> = (0..10).map(|i| format!("item {i}")).collect();
+    let mut selection: HashSet<Id> = HashSet::new();
+    // The last clicked item, used as the anchor of shift-click range selections
+    let mut anchor: Option<Id> = None;
+
+    eframe::run_ui_native(
+        "DnD Multi Select Example",
+        NativeOptions::default(),
+        move |ui, _frame| {
+            CentralPanel::default().show(ui, |ui| {
+                ui.label("Ctrl/Cmd-click to select multiple items, shift-click to select a range.");
+
+                let mut clicked = None;
+                dnd(ui, "dnd_multi_select_example")
+                    .with_selection(selection.iter().copied())
+                    .show_vec(&mut items, |ui, item, handle, _state| {
+                        let id = Id::new(&*item);
+                        let response = handle.sense(Sense::click()).ui(ui, |ui| {
+                            ui.selectable_label(selection.contains(&id), item.as_str());
+                        });
+                        if response.clicked() {
+                            clicked = Some(id);
+                        }
+                    });
+
+                if let Some(id) = clicked {
+                    let modifiers = ui.input(|i| i.modifiers);
+                    if modifiers.shift {
+                        let ids: Vec<Id> = items.iter().map(|item| Id::new(item)).collect();
+                        let anchor_idx = anchor.and_then(|a| ids.iter().position(|i| *i == a));
+                        let clicked_idx = ids.iter().position(|i| *i == id);
+                        if let (Some(a), Some(b)) = (anchor_idx, clicked_idx) {
+                            selection.extend(&ids[a.min(b)..=a.max(b)]);
+                        }
+                    } else if modifiers.command {
+                        if !selection.remove(&id) {
+                            selection.insert(id);
+                        }
+                        anchor = Some(id);
+                    } else {
+                        selection.clear();
+                        selection.insert(id);
+                        anchor = Some(id);
+                    }
+                }
+            });
+        },
+    )
+}
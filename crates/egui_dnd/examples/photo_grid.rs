@@ -0,0 +1,63 @@
+use eframe::{egui, NativeOptions};
+use egui::{CentralPanel, Color32, Frame, Id, Label, ScrollArea, Vec2, Widget};
+use egui_dnd::dnd;
+
+struct Photo {
+    id: usize,
+    size: Vec2,
+    color: Color32,
+}
+
+pub fn main() -> eframe::Result<()> {
+    // Photos with different aspect ratios, like in a gallery
+    let mut photos: Vec<_> = (0..40)
+        .map(|id| Photo {
+            id,
+            size: match id % 4 {
+                0 => Vec2::new(160.0, 100.0),
+                1 => Vec2::new(80.0, 100.0),
+                2 => Vec2::new(120.0, 80.0),
+                _ => Vec2::new(100.0, 100.0),
+            },
+            color: Color32::from_rgb(
+                (id * 53 % 200) as u8 + 40,
+                (id * 97 % 200) as u8 + 40,
+                (id * 31 % 200) as u8 + 40,
+            ),
+        })
+        .collect();
+
+    eframe::run_ui_native(
+        "DnD Photo Grid Example",
+        NativeOptions::default(),
+        move |ui, _frame| {
+            CentralPanel::default().show(ui, |ui| {
+                ScrollArea::vertical().show(ui, |ui| {
+                    ui.set_width(ui.available_width());
+                    ui.horizontal_wrapped(|ui| {
+                        let response = dnd(ui, "photo_grid").show_custom(|ui, iter| {
+                            for (idx, photo) in photos.iter().enumerate() {
+                                iter.next(ui, Id::new(photo.id), idx, true, |ui, item| {
+                                    // Use ui_sized so the item is placed in the right row
+                                    // before its content is drawn
+                                    item.ui_sized(ui, photo.size, |ui, handle, _state| {
+                                        handle.ui_sized(ui, photo.size, |ui| {
+                                            Frame::NONE.fill(photo.color).show(ui, |ui| {
+                                                ui.set_min_size(photo.size);
+                                                ui.centered_and_justified(|ui| {
+                                                    Label::new(format!("Photo {}", photo.id))
+                                                        .ui(ui);
+                                                });
+                                            });
+                                        });
+                                    })
+                                });
+                            }
+                        });
+                        response.update_vec(&mut photos);
+                    });
+                });
+            });
+        },
+    )
+}
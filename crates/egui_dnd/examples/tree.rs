@@ -0,0 +1,41 @@
+use eframe::{egui, NativeOptions};
+use egui::CentralPanel;
+use egui_dnd::dnd;
+use egui_dnd::utils::shift_tree;
+
+pub fn main() -> eframe::Result<()> {
+    // The tree is stored as a flat list of items with their depth, in depth first order
+    let mut tree = vec![
+        ("src".to_string(), 0),
+        ("main.rs".to_string(), 1),
+        ("utils".to_string(), 1),
+        ("mod.rs".to_string(), 2),
+        ("assets".to_string(), 0),
+        ("icon.png".to_string(), 1),
+        ("Cargo.toml".to_string(), 0),
+        ("README.md".to_string(), 0),
+    ];
+
+    eframe::run_ui_native(
+        "DnD Tree Example",
+        NativeOptions::default(),
+        move |ui, _frame| {
+            CentralPanel::default().show(ui, |ui| {
+                ui.label("Drag items left or right to change their parent.");
+
+                let response = dnd(ui, "dnd_tree_example").show_tree(
+                    tree.iter().map(|(name, depth)| (name, *depth)),
+                    |ui, name, handle, _state| {
+                        handle.ui(ui, |ui| {
+                            ui.label(name);
+                        });
+                    },
+                );
+
+                if let Some(update) = response.final_tree_update() {
+                    shift_tree(&update, &mut tree);
+                }
+            });
+        },
+    )
+}
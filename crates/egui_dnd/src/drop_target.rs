@@ -0,0 +1,208 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use egui::epaint::RectShape;
+use egui::{AsId, Context, Id, InnerResponse, Pos2, Rect, Shape, StrokeKind, Ui};
+
+use crate::group::AcceptFn;
+
+/// An item that was dropped on a [`DropTarget`]
+#[derive(Debug, Clone)]
+pub struct ExternalDrop {
+    /// Id of the drop target (`Id::new(id_source)` of [`DropTarget::new`])
+    pub target: Id,
+    /// Id of the dropped item
+    pub item: Id,
+    /// Index of the dropped item in the list
+    pub from: usize,
+}
+
+#[derive(Debug, Clone)]
+struct RegisteredTarget {
+    rect: Rect,
+    frame_nr: u64,
+    accept: Option<AcceptFn>,
+}
+
+/// The drop targets of a list, stored in egui's temp data
+#[derive(Debug, Clone, Default)]
+pub(crate) struct DropTargets {
+    targets: HashMap<Id, RegisteredTarget>,
+    /// The target that the dragged item is hovering
+    hovered: Option<(Id, Id)>,
+    /// The last item that was dropped, until the target picks it up
+    dropped: Option<(Id, Id)>,
+}
+
+impl DropTargets {
+    fn id(list: Id) -> Id {
+        list.with("egui_dnd_drop_targets")
+    }
+
+    pub fn with<R>(ctx: &Context, list: Id, f: impl FnOnce(&mut Self) -> R) -> R {
+        ctx.data_mut(|data| f(data.get_temp_mut_or_default::<Self>(Self::id(list))))
+    }
+
+    /// Update which target the dragged item is hovering, returns the target's id.
+    /// Pass `None` if no item is dragged.
+    pub fn update_hovered(&mut self, frame_nr: u64, dragged: Option<(Id, Pos2)>) -> Option<Id> {
+        self.hovered = dragged.and_then(|(item, pointer)| {
+            self.targets
+                .iter()
+                .filter(|(_, target)| {
+                    target.frame_nr + 1 >= frame_nr
+                        && target.rect.contains(pointer)
+                        && target.accept.as_ref().is_none_or(|accept| (accept.0)(item))
+                })
+                .min_by(|(_, a), (_, b)| a.rect.area().total_cmp(&b.rect.area()))
+                .map(|(target, _)| (*target, item))
+        });
+        self.hovered.map(|(target, _)| target)
+    }
+
+    pub fn drop(&mut self, target: Id, item: Id) {
+        self.hovered = None;
+        self.dropped = Some((target, item));
+    }
+}
+
+/// A widget that items of a [`crate::dnd`] list can be dropped on, e.g. a trash zone or a
+/// folder in a sidebar. It is highlighted while a compatible item is dragged over it.
+/// When the item is dropped, [`DropTargetResponse::dropped_item`] contains the item's id
+/// and the list's [`crate::DragDropResponse::external_drop`] is set instead of an update.
+///
+/// The target can be shown before or after the list. If it's shown before the list, it
+/// receives the item one frame after the drop.
+///
+/// ```rust no_run
+/// use eframe::egui;
+/// use egui::CentralPanel;
+/// use egui_dnd::{dnd, DropTarget};
+///
+/// pub fn main() -> eframe::Result<()> {
+///     let mut items = vec!["alfred", "bernhard", "christian"];
+///
+///     eframe::run_ui_native("DnD Drop Target Example", Default::default(), move |ui, _frame| {
+///         CentralPanel::default().show(ui, |ui| {
+///
+///             let response = dnd(ui, "list").show_vec(&mut items, |ui, item, handle, _state| {
+///                 handle.ui(ui, |ui| {
+///                     ui.label(*item);
+///                 });
+///             });
+///
+///             DropTarget::new("list", "trash").show(ui, |ui, _hovered| {
+///                 ui.label("🗑 Drop here to delete");
+///             });
+///
+///             if let Some(drop) = response.final_external_drop() {
+///                 items.remove(drop.from);
+///             }
+///
+///         });
+///     })
+/// }
+/// ```
+pub struct DropTarget {
+    list: Id,
+    id: Id,
+    accept: Option<AcceptFn>,
+    highlight: bool,
+}
+
+/// The response of [`DropTarget::show`]
+pub struct DropTargetResponse<R> {
+    /// The value returned by the content and the response of the target's area
+    pub inner: InnerResponse<R>,
+    /// The id of the compatible item that is dragged over the target
+    pub hovered_item: Option<Id>,
+    /// The id of the item that was dropped on the target in this frame
+    pub dropped_item: Option<Id>,
+}
+
+impl DropTarget {
+    /// Create a drop target for the list with the `list_id_source` passed to [`crate::dnd`].
+    /// `id_source` identifies the target, it needs to be unique among the targets of the list.
+    pub fn new(list_id_source: impl AsId, id_source: impl AsId) -> Self {
+        Self {
+            list: Id::new(list_id_source),
+            id: Id::new(id_source),
+            accept: None,
+            highlight: true,
+        }
+    }
+
+    /// Only accept the items for which `accept` returns true. It's called with the item id.
+    /// By default, all items are accepted.
+    #[must_use]
+    pub fn with_accept(mut self, accept: impl Fn(Id) -> bool + Send + Sync + 'static) -> Self {
+        self.accept = Some(AcceptFn(Arc::new(accept)));
+        self
+    }
+
+    /// Whether to highlight the target while a compatible item is dragged over it.
+    /// The default is true. You can also use the `hovered` argument of the content to style it.
+    #[must_use]
+    pub fn with_highlight(mut self, highlight: bool) -> Self {
+        self.highlight = highlight;
+        self
+    }
+
+    /// Show the target. `content` gets true while a compatible item is dragged over it.
+    pub fn show<R>(
+        self,
+        ui: &mut Ui,
+        content: impl FnOnce(&mut Ui, bool) -> R,
+    ) -> DropTargetResponse<R> {
+        let (hovered_item, dropped_item) = DropTargets::with(ui.ctx(), self.list, |targets| {
+            let hovered_item = targets
+                .hovered
+                .filter(|(target, _)| *target == self.id)
+                .map(|(_, item)| item);
+            let dropped_item = if targets.dropped.is_some_and(|(target, _)| target == self.id) {
+                targets.dropped.take().map(|(_, item)| item)
+            } else {
+                None
+            };
+            (hovered_item, dropped_item)
+        });
+
+        // Reserve a shape behind the content for the highlight, we only know its rect afterwards
+        let highlight = ui.painter().add(Shape::Noop);
+        let inner = ui.scope(|ui| content(ui, hovered_item.is_some()));
+        let rect = inner.response.rect;
+
+        if self.highlight && hovered_item.is_some() {
+            let selection = ui.visuals().selection;
+            ui.painter().set(
+                highlight,
+                RectShape::new(
+                    rect,
+                    ui.visuals().widgets.hovered.corner_radius,
+                    selection.bg_fill.gamma_multiply(0.25),
+                    selection.stroke,
+                    StrokeKind::Outside,
+                ),
+            );
+        }
+
+        let frame_nr = ui.ctx().cumulative_frame_nr();
+        let clip_rect = ui.clip_rect();
+        DropTargets::with(ui.ctx(), self.list, |targets| {
+            targets.targets.insert(
+                self.id,
+                RegisteredTarget {
+                    rect: rect.intersect(clip_rect),
+                    frame_nr,
+                    accept: self.accept,
+                },
+            );
+        });
+
+        DropTargetResponse {
+            inner,
+            hovered_item,
+            dropped_item,
+        }
+    }
+}
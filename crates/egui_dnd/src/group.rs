@@ -0,0 +1,81 @@
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+
+use egui::{Context, Id, Pos2, Rect};
+
+/// Decides whether a list accepts items dragged from the list with the given id.
+/// See [`crate::Dnd::with_accept`].
+#[derive(Clone)]
+pub(crate) struct AcceptFn(pub Arc<dyn Fn(Id) -> bool + Send + Sync>);
+
+impl Debug for AcceptFn {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("AcceptFn")
+    }
+}
+
+/// An item that is dragged from one list of a group, stored in the [`DndGroup`]
+#[derive(Debug, Clone)]
+pub(crate) struct GroupDrag {
+    pub source_list: Id,
+    pub source_idx: usize,
+    pub item: Id,
+    /// The rect of the floating item
+    pub rect: Rect,
+    /// The list of the group the item is hovering, if it's not the source list
+    pub target_list: Option<Id>,
+}
+
+#[derive(Debug, Clone)]
+struct GroupList {
+    rect: Rect,
+    frame_nr: u64,
+    accept: Option<AcceptFn>,
+}
+
+/// Shared state of the lists in a group, see [`crate::Dnd::with_group`].
+/// Each list registers the area it was shown in, so the list an item is dragged from can
+/// tell which list the item is hovering.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct DndGroup {
+    lists: HashMap<Id, GroupList>,
+    pub drag: Option<GroupDrag>,
+}
+
+impl DndGroup {
+    fn id(group: Id) -> Id {
+        group.with("egui_dnd_group")
+    }
+
+    pub fn with<R>(ctx: &Context, group: Id, f: impl FnOnce(&mut Self) -> R) -> R {
+        ctx.data_mut(|data| f(data.get_temp_mut_or_default::<Self>(Self::id(group))))
+    }
+
+    pub fn register(&mut self, frame_nr: u64, list: Id, rect: Rect, accept: Option<AcceptFn>) {
+        self.lists.insert(
+            list,
+            GroupList {
+                rect,
+                frame_nr,
+                accept,
+            },
+        );
+    }
+
+    /// The list hovered by the pointer, if it's not the source list and accepts items from it.
+    /// If lists are nested, the innermost (smallest) one is used.
+    pub fn target_list(&self, frame_nr: u64, source_list: Id, pointer: Pos2) -> Option<Id> {
+        let (list, entry) = self
+            .lists
+            .iter()
+            .filter(|(_, entry)| entry.frame_nr + 1 >= frame_nr && entry.rect.contains(pointer))
+            .min_by(|(_, a), (_, b)| a.rect.area().total_cmp(&b.rect.area()))?;
+
+        let accepts = entry
+            .accept
+            .as_ref()
+            .is_none_or(|accept| (accept.0)(source_list));
+        (*list != source_list && accepts).then_some(*list)
+    }
+}
@@ -1,5 +1,10 @@
-use egui::{CursorIcon, Id, InnerResponse, Layout, Order, Pos2, Rect, Sense, Ui, UiBuilder, Vec2};
+use egui::{
+    Align2, CursorIcon, FontId, Id, InnerResponse, Layout, Order, Pos2, Rect, Sense, StrokeKind,
+    Ui, UiBuilder, Vec2,
+};
 use egui_animation::animate_position;
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
 
 use crate::state::DragDetectionState;
 use crate::{DragDropUi, Handle, ItemState};
@@ -95,6 +100,7 @@ impl<'a> Item<'a> {
                     false,
                 );
 
+                let dragged_count = self.dnd_state.dragged_count();
                 let InnerResponse { inner: rect, .. } = Self::draw_floating_at_position(
                     self.state,
                     self.dnd_state,
@@ -105,6 +111,7 @@ impl<'a> Item<'a> {
                     hovering_over_any_handle,
                     size,
                     *ui.layout(),
+                    dragged_count,
                     drag_body,
                 );
 
@@ -145,6 +152,7 @@ impl<'a> Item<'a> {
                     hovering_over_any_handle,
                     size,
                     *ui.layout(),
+                    1,
                     drag_body,
                 );
 
@@ -180,7 +188,7 @@ impl<'a> Item<'a> {
                 true,
             );
 
-            let position = if self.dnd_state.detection_state.is_dragging() {
+            let position = if self.dnd_state.animates_items() {
                 animated_position
             } else {
                 rect.min
@@ -220,7 +228,7 @@ impl<'a> Item<'a> {
                 true,
             );
 
-            let position = if self.dnd_state.detection_state.is_dragging() {
+            let position = if self.dnd_state.animates_items() {
                 animated_position
             } else {
                 position
@@ -275,6 +283,7 @@ impl<'a> Item<'a> {
         hovering_over_any_handle: &mut bool,
         size: Option<Vec2>,
         layout: Layout,
+        stacked_count: usize,
         body: impl FnOnce(&mut Ui, Handle, ItemState),
     ) -> InnerResponse<Rect> {
         let transform = ui.ctx().layer_transform_to_global(ui.layer_id());
@@ -288,20 +297,118 @@ impl<'a> Item<'a> {
                     ui.ctx().set_transform_layer(ui.layer_id(), transform);
                 }
 
-                ui.scope_builder(UiBuilder::new().layout(layout).id(unique_id), |ui| {
-                    if let Some(size) = size.or(dnd_state.detection_state.dragged_item_size()) {
-                        ui.set_max_size(size);
+                let item_size = size.or(dnd_state.detection_state.dragged_item_size());
+                let preview = dnd_state
+                    .drag_preview
+                    .clone()
+                    .filter(|_| dnd_state.detection_state.is_dragging());
+                if let Some(preview) = preview {
+                    let preview_info = DragPreview {
+                        id,
+                        index: state.index,
+                        count: stacked_count,
+                        rect: Rect::from_min_size(pos, item_size.unwrap_or_default()),
+                    };
+                    return Self::draw_preview(ui, unique_id, layout, &preview, &preview_info);
+                }
+
+                if stacked_count > 1 {
+                    if let Some(item_size) = item_size {
+                        Self::paint_stack(ui, Rect::from_min_size(pos, item_size), stacked_count);
                     }
-                    body(
-                        ui,
-                        Handle::new(id, state.index, dnd_state, hovering_over_any_handle, pos),
-                        state,
-                    );
-                })
-                .response
-                .rect
+                }
+
+                let rect = ui
+                    .scope_builder(UiBuilder::new().layout(layout).id(unique_id), |ui| {
+                        if let Some(size) = item_size {
+                            ui.set_max_size(size);
+                        }
+                        body(
+                            ui,
+                            Handle::new(id, state.index, dnd_state, hovering_over_any_handle, pos),
+                            state,
+                        );
+                    })
+                    .response
+                    .rect;
+
+                if stacked_count > 1 {
+                    Self::paint_count_badge(ui, rect, stacked_count);
+                }
+                rect
             })
     }
+
+    /// Draw the dragged item with the [`crate::Dnd::with_drag_preview`] painter
+    fn draw_preview(
+        ui: &mut Ui,
+        unique_id: Id,
+        layout: Layout,
+        preview: &DragPreviewFn,
+        info: &DragPreview,
+    ) -> Rect {
+        ui.scope_builder(UiBuilder::new().layout(layout).id(unique_id), |ui| {
+            // Keep the size of the item, so the gap doesn't change
+            ui.set_min_size(info.rect.size());
+            ui.set_max_size(info.rect.size());
+            (preview.0)(ui, info);
+        })
+        .response
+        .rect
+    }
+
+    /// Paint a stack of cards behind the dragged item when multiple items are dragged
+    fn paint_stack(ui: &Ui, rect: Rect, count: usize) {
+        let visuals = &ui.visuals().widgets.noninteractive;
+        let layers = u8::try_from(count.min(3)).unwrap_or(3);
+        for layer in (0..layers).rev() {
+            ui.painter().rect(
+                rect.translate(Vec2::splat(4.0 * f32::from(layer))),
+                visuals.corner_radius,
+                ui.visuals().window_fill,
+                visuals.bg_stroke,
+                StrokeKind::Inside,
+            );
+        }
+    }
+
+    /// Paint the number of dragged items at the top right corner of the dragged item
+    fn paint_count_badge(ui: &Ui, rect: Rect, count: usize) {
+        let selection = &ui.visuals().selection;
+        ui.painter()
+            .circle_filled(rect.right_top(), 9.0, selection.bg_fill);
+        ui.painter().text(
+            rect.right_top(),
+            Align2::CENTER_CENTER,
+            count.to_string(),
+            FontId::proportional(11.0),
+            selection.stroke.color,
+        );
+    }
 }
 
 pub struct ItemResponse(pub(crate) Rect);
+
+/// Info about the dragged item, passed to the [`crate::Dnd::with_drag_preview`] painter
+#[derive(Debug, Clone, Copy)]
+pub struct DragPreview {
+    /// The id of the dragged item
+    pub id: Id,
+    /// The index of the dragged item
+    pub index: usize,
+    /// The number of dragged items, greater than 1 when multiple selected items are dragged
+    pub count: usize,
+    /// Where the dragged item is drawn, with the size of the item in the list
+    pub rect: Rect,
+}
+
+/// Draws the dragged item, see [`crate::Dnd::with_drag_preview`]
+#[derive(Clone)]
+#[allow(clippy::type_complexity)]
+pub(crate) struct DragPreviewFn(pub Arc<dyn Fn(&mut Ui, &DragPreview) + Send + Sync>);
+
+impl Debug for DragPreviewFn {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("DragPreviewFn")
+    }
+}
@@ -1,7 +1,8 @@
 use crate::item::{Item, ItemResponse};
 use crate::state::DragDetectionState;
-use crate::{DragDropUi, ItemState};
-use egui::{Id, Layout, Pos2, Rect, Ui, UiBuilder, Vec2};
+use crate::tree::TreeEntry;
+use crate::{DragDropUi, InsertionIndicator, ItemState};
+use egui::{Direction, Id, Layout, Pos2, Rect, Sense, StrokeKind, Ui, UiBuilder, Vec2};
 
 /// Calculates some information that is later used to detect in which index the dragged item should be placed.
 /// [`ItemIterator::next`] should be called for each item in the list.
@@ -20,6 +21,17 @@ pub struct ItemIterator<'a> {
     pub(crate) is_after_hovered_item: bool,
     pub(crate) hovering_over_any_handle: bool,
     pub(crate) source_item: Option<(usize, Id)>,
+    pub(crate) item_count: usize,
+    /// Indices of the selected items while dragging multiple items
+    pub(crate) selected_indices: Vec<usize>,
+    /// Indices of the items other items can't be moved past
+    pub(crate) fixed_indices: Vec<usize>,
+    /// The items shown with [`ItemIterator::next_in_tree`]
+    pub(crate) tree: Vec<TreeEntry>,
+    /// The depth of the dragged item, while its children are skipped
+    dragged_subtree_depth: Option<usize>,
+    /// The space reserved for the dragged item
+    pub(crate) gap_rect: Option<Rect>,
 
     #[allow(clippy::type_complexity)]
     pub(crate) closest_item: Option<(f32, Option<(usize, Id, Pos2)>)>,
@@ -60,6 +72,12 @@ impl<'a> ItemIterator<'a> {
             is_after_hovered_item: false,
             hovering_over_any_handle: false,
             source_item: None,
+            item_count: 0,
+            selected_indices: Vec::new(),
+            fixed_indices: Vec::new(),
+            tree: Vec::new(),
+            dragged_subtree_depth: None,
+            gap_rect: None,
         }
     }
 
@@ -76,6 +94,18 @@ impl<'a> ItemIterator<'a> {
         add_surrounding_space_automatically: bool,
         content: impl FnOnce(&mut Ui, Item) -> ItemResponse,
     ) {
+        self.item_count = self.item_count.max(idx + 1);
+        if self.state.fixed.contains(&id) {
+            self.fixed_indices.push(idx);
+        }
+        if self.state.is_multi_drag() && self.state.selection.contains(&id) {
+            self.selected_indices.push(idx);
+            if self.state.is_carried(id) {
+                // The item is shown in the stacked preview of the dragged item
+                return;
+            }
+        }
+
         let is_dragged_item = self.state.detection_state.is_dragging_item(id);
 
         if let Some((distance, pos)) = self.mark_next_as_closest_item {
@@ -121,7 +151,13 @@ impl<'a> ItemIterator<'a> {
                     )
                     .inner
                     .0;
-                Rect::from_min_size(start, rect.size())
+                if self.state.placeholder
+                    && self.state.insertion_indicator != InsertionIndicator::Gap
+                {
+                    Self::placeholder(ui, rect.size())
+                } else {
+                    Rect::from_min_size(start, rect.size())
+                }
             } else {
                 content(ui, item).0
             }
@@ -138,13 +174,11 @@ impl<'a> ItemIterator<'a> {
         }
 
         if let Some(dragged_item_rect) = self.dragged_item_rect {
-            if self.layout.main_wrap {
-                if rect.contains(dragged_item_rect.center()) {
-                    if self.is_after_hovered_item {
-                        self.mark_next_as_closest_item = Some((0.0, rect.min));
-                    } else {
-                        self.closest_item = Some((0.0, Some((idx, id, rect.min))));
-                    }
+            // Without a gap, the items stay in place, so we can always use the slot hit testing
+            if self.layout.main_wrap || self.state.insertion_indicator != InsertionIndicator::Gap {
+                // The dragged item doesn't take up a slot, the gap (or placeholder) does
+                if !is_dragged_item {
+                    self.check_closest_slot(dragged_item_rect, rect, idx, id);
                 }
             } else {
                 let (distance, mark_next) = self.get_distance(dragged_item_rect, rect);
@@ -159,6 +193,71 @@ impl<'a> ItemIterator<'a> {
         self.last_item = Some((idx, id, rect.min));
     }
 
+    /// Draw an item of a tree, see [`crate::Dnd::show_tree`]. The items of the tree need to be
+    /// passed in depth first order (each item followed by its children), `depth` is 0 for the
+    /// items at the root level. `idx` is the index in this flat list.
+    ///
+    /// You need to indent the item yourself, by `depth` times the indent set with
+    /// [`crate::Dnd::with_tree_indent`].
+    /// When items are shown like this, [`crate::DragDropResponse::tree_update`] is set instead
+    /// of [`crate::DragDropResponse::update`].
+    pub fn next_in_tree(
+        &mut self,
+        ui: &mut Ui,
+        id: Id,
+        idx: usize,
+        depth: usize,
+        add_surrounding_space_automatically: bool,
+        content: impl FnOnce(&mut Ui, Item) -> ItemResponse,
+    ) {
+        let hidden = self
+            .dragged_subtree_depth
+            .is_some_and(|dragged_depth| depth > dragged_depth);
+        if !hidden {
+            self.dragged_subtree_depth = None;
+        }
+        if self.state.detection_state.dragged_item() == Some(id) {
+            self.dragged_subtree_depth = Some(depth);
+        }
+
+        self.tree.push(TreeEntry {
+            idx,
+            id,
+            depth,
+            hidden,
+        });
+
+        // The children of the dragged item are moved together with it
+        if !hidden {
+            self.next(ui, id, idx, add_surrounding_space_automatically, content);
+        }
+    }
+
+    /// 2D hit testing for wrapping layouts (grids and wrapped rows): The item closest to the
+    /// center of the dragged item wins, and the dragged item is placed before or after it,
+    /// depending on which half of the item is hovered.
+    /// Since the distance to the item's rect is used, this also works for the empty space
+    /// between items or at the end of a row, and for items of different sizes.
+    fn check_closest_slot(&mut self, dragged_item_rect: Rect, rect: Rect, idx: usize, id: Id) {
+        let center = dragged_item_rect.center();
+        let distance = rect.distance_to_pos(center);
+        let after = match self.layout.main_dir {
+            Direction::LeftToRight => center.x > rect.center().x,
+            Direction::RightToLeft => center.x < rect.center().x,
+            Direction::TopDown => center.y > rect.center().y,
+            Direction::BottomUp => center.y < rect.center().y,
+        };
+        let is_hovered_item = self
+            .hovering_item
+            .is_some_and(|(hovering_id, _)| hovering_id == id);
+        if after && self.hovering_last_item && is_hovered_item {
+            // The gap is already after the last item, keep it there
+            self.check_closest_item(distance, rect.min, None, false);
+        } else {
+            self.check_closest_item(distance, rect.min, Some((idx, id)), after);
+        }
+    }
+
     fn get_distance(&mut self, dragged_item_rect: Rect, rect: Rect) -> (f32, bool) {
         let size_difference = dragged_item_rect.size() - rect.size();
         let (distance, mark_next) = if self.layout.is_horizontal() {
@@ -201,6 +300,11 @@ impl<'a> ItemIterator<'a> {
         if let Some((hovering_id, _pos)) = self.hovering_item {
             if hovering_id == id {
                 if let Some(dragged_item_rect) = self.dragged_item_rect {
+                    if self.state.insertion_indicator != InsertionIndicator::Gap {
+                        // The items stay in place, we only need to know where to draw the line
+                        self.gap_rect = Some(Self::line_rect(ui, dragged_item_rect.size()));
+                        return;
+                    }
                     let rect = ui
                         .allocate_ui(dragged_item_rect.size(), |ui| {
                             ui.set_min_size(dragged_item_rect.size());
@@ -208,13 +312,50 @@ impl<'a> ItemIterator<'a> {
                         })
                         .response
                         .rect;
-                    let (distance, _mark_next) = self.get_distance(dragged_item_rect, rect);
+                    self.gap_rect = Some(rect);
+                    let distance = if self.layout.main_wrap {
+                        // Prefer the gap, so the target doesn't flip back and forth when moving
+                        // the gap makes the rows reflow
+                        let hysteresis = dragged_item_rect.size().min_elem() / 2.0;
+                        rect.distance_to_pos(dragged_item_rect.center()) - hysteresis
+                    } else {
+                        self.get_distance(dragged_item_rect, rect).0
+                    };
                     self.check_closest_item(distance, rect.min, None, false);
                 }
             }
         }
     }
 
+    /// A rect with zero size on the main axis, at the position the next item would be placed at
+    fn line_rect(ui: &Ui, size: Vec2) -> Rect {
+        let cursor = ui.cursor();
+        match ui.layout().main_dir {
+            Direction::LeftToRight => Rect::from_min_size(cursor.min, Vec2::new(0.0, size.y)),
+            Direction::RightToLeft => {
+                Rect::from_min_size(cursor.right_top(), Vec2::new(0.0, size.y))
+            }
+            Direction::TopDown => Rect::from_min_size(cursor.min, Vec2::new(size.x, 0.0)),
+            Direction::BottomUp => {
+                Rect::from_min_size(cursor.left_bottom(), Vec2::new(size.x, 0.0))
+            }
+        }
+    }
+
+    /// Keep the original slot of the dragged item and draw a placeholder in it
+    fn placeholder(ui: &mut Ui, size: Vec2) -> Rect {
+        let (rect, _) = ui.allocate_exact_size(size, Sense::hover());
+        let visuals = &ui.visuals().widgets.noninteractive;
+        ui.painter().rect(
+            rect,
+            visuals.corner_radius,
+            ui.visuals().faint_bg_color,
+            visuals.bg_stroke,
+            StrokeKind::Inside,
+        );
+        rect
+    }
+
     fn check_closest_item(
         &mut self,
         distance: f32,
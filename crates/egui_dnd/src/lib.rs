@@ -3,14 +3,25 @@
 #![warn(missing_docs)]
 
 use egui::{AsId, Id, Ui};
-pub use state::{DragAxis, DragDropConfig, DragDropItem, DragDropResponse, DragUpdate, Handle};
+pub use state::{
+    CrossListUpdate, DragAxis, DragDropConfig, DragDropItem, DragDropResponse, DragUpdate, Handle,
+    InsertionIndicator, MultiDragUpdate, TreeUpdate,
+};
 
+pub use crate::drop_target::{DropTarget, DropTargetResponse, ExternalDrop};
+use crate::group::AcceptFn;
+pub use crate::item::DragPreview;
+use crate::item::DragPreviewFn;
 pub use crate::item_iterator::ItemIterator;
-use crate::state::DragDropUi;
+use crate::state::{DragDropUi, ValidateFn};
+use std::sync::Arc;
 
+mod drop_target;
+mod group;
 mod item;
 mod item_iterator;
 mod state;
+mod tree;
 /// Helper functions to support the drag and drop functionality
 pub mod utils;
 
@@ -50,12 +61,25 @@ pub struct Dnd<'a> {
 /// }
 /// ```
 pub fn dnd(ui: &mut Ui, id_source: impl AsId) -> Dnd<'_> {
-    let id = Id::new(id_source).with("dnd");
+    let list_id = Id::new(id_source);
+    let id = list_id.with("dnd");
     let mut dnd_ui: DragDropUi =
         ui.data_mut(|data| (*data.get_temp_mut_or_default::<DragDropUi>(id)).clone());
 
     dnd_ui.return_animation_time = ui.style().animation_time;
     dnd_ui.swap_animation_time = ui.style().animation_time;
+    dnd_ui.tree_indent = ui.spacing().indent;
+    dnd_ui.list_id = list_id;
+    dnd_ui.group = None;
+    dnd_ui.accept = None;
+    dnd_ui.selection.clear();
+    dnd_ui.insertion_indicator = InsertionIndicator::Gap;
+    dnd_ui.placeholder = false;
+    dnd_ui.drag_preview = None;
+    dnd_ui.animated_move = None;
+    dnd_ui.locked.clear();
+    dnd_ui.fixed.clear();
+    dnd_ui.validate = None;
 
     Dnd {
         id,
@@ -125,6 +149,138 @@ impl<'a> Dnd<'a> {
         self
     }
 
+    /// Allows dragging items between all lists with the same group id, e.g. the columns of a
+    /// kanban board. **Item ids need to be unique across all lists of the group.**
+    ///
+    /// Items can be dropped anywhere in the area of the [`Ui`] the list is shown in, so show
+    /// each list in its own [`Ui`] (e.g. with [`Ui::columns`]).
+    /// While an item hovers another list, that list's [`DragDropResponse::cross_list_update`]
+    /// tells where it would be inserted and the list it came from reports no update.
+    /// Check the [kanban example](https://github.com/lucasmerlin/hello_egui/blob/main/crates/egui_dnd/examples/kanban.rs).
+    #[must_use]
+    pub fn with_group(mut self, group: impl AsId) -> Self {
+        self.drag_drop_ui.group = Some(Id::new(group));
+        self
+    }
+
+    /// Only accept items dragged from the lists of the group for which `accept` returns true.
+    /// It's called with the list id (`Id::new(id_source)` of the other list's [`dnd`] call).
+    /// By default, items from all lists of the group are accepted.
+    #[must_use]
+    pub fn with_accept(mut self, accept: impl Fn(Id) -> bool + Send + Sync + 'static) -> Self {
+        self.drag_drop_ui.accept = Some(AcceptFn(Arc::new(accept)));
+        self
+    }
+
+    /// Sets the ids of the selected items (see [`DragDropItem::id`]).
+    /// When one of the selected items is dragged, all of them are dragged together: they
+    /// collapse into a stacked preview under the pointer and [`DragDropResponse::multi_update`]
+    /// describes the block move instead of [`DragDropResponse::update`].
+    /// [`DragDropResponse::update_vec`] (and therefore [`Dnd::show_vec`]) applies it, keeping
+    /// the relative order of the selected items.
+    ///
+    /// Selecting the items (e.g. with ctrl/shift-click) is up to you, check the
+    /// [multi select example](https://github.com/lucasmerlin/hello_egui/blob/main/crates/egui_dnd/examples/multi_select.rs).
+    #[must_use]
+    pub fn with_selection(mut self, selection: impl IntoIterator<Item = Id>) -> Self {
+        self.drag_drop_ui.selection = selection.into_iter().collect();
+        self
+    }
+
+    /// Sets how far the items of a tree are indented per level, see [`Dnd::show_tree`].
+    /// Dragging an item horizontally by this distance changes its depth by one level.
+    /// The default is the indent of the ui's spacing.
+    #[must_use]
+    pub fn with_tree_indent(mut self, indent: f32) -> Self {
+        self.drag_drop_ui.tree_indent = indent;
+        self
+    }
+
+    /// Sets the ids of the items that can't be dragged (see [`DragDropItem::id`]), e.g. pinned
+    /// rows. Other items can still be moved around them, use [`Dnd::with_fixed_items`] to keep
+    /// them in place.
+    #[must_use]
+    pub fn with_locked_items(mut self, ids: impl IntoIterator<Item = Id>) -> Self {
+        self.drag_drop_ui.locked = ids.into_iter().collect();
+        self
+    }
+
+    /// Sets the ids of the items that aren't drop targets (see [`DragDropItem::id`]): the dragged
+    /// item can't take their place or be moved past them, so they keep their position.
+    /// E.g. a header that should always be the first item should be locked
+    /// ([`Dnd::with_locked_items`]) and fixed.
+    /// When multiple selected items are dragged, none of them can be moved past a fixed item.
+    /// Items dragged in from another list of the group (see [`Dnd::with_group`]) are only
+    /// checked by [`Dnd::with_validation`].
+    #[must_use]
+    pub fn with_fixed_items(mut self, ids: impl IntoIterator<Item = Id>) -> Self {
+        self.drag_drop_ui.fixed = ids.into_iter().collect();
+        self
+    }
+
+    /// Sets a callback that decides whether the dragged item may be moved to a position.
+    /// It's called with the proposed update (relative to the list as it's currently shown)
+    /// before the gap moves, so the gap and the swap animation are never shown for a rejected
+    /// position. Return `false` to keep the item where it is, e.g. to prevent moving items
+    /// between sections. This also applies to keyboard reordering.
+    /// When multiple selected items are dragged (see [`Dnd::with_selection`]), it's called for
+    /// each of them and all of them have to be accepted.
+    /// For items dragged in from another list of the group, `from` is the index in that list.
+    #[must_use]
+    pub fn with_validation(
+        mut self,
+        validate: impl Fn(&DragUpdate) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.drag_drop_ui.validate = Some(ValidateFn(Arc::new(validate)));
+        self
+    }
+
+    /// Sets how the position the dragged item would be dropped at is shown.
+    /// With [`InsertionIndicator::Line`] or [`InsertionIndicator::None`] the items stay in place
+    /// while dragging, instead of making room for the dragged item.
+    /// The default is [`InsertionIndicator::Gap`].
+    #[must_use]
+    pub fn with_insertion_indicator(mut self, indicator: InsertionIndicator) -> Self {
+        self.drag_drop_ui.insertion_indicator = indicator;
+        self
+    }
+
+    /// If true, the slot the dragged item was dragged from keeps its size and is drawn as a
+    /// placeholder, so the list doesn't collapse while dragging.
+    /// This only has an effect with [`InsertionIndicator::Line`] or [`InsertionIndicator::None`],
+    /// since the gap already starts at the original slot.
+    #[must_use]
+    pub fn with_placeholder(mut self, placeholder: bool) -> Self {
+        self.drag_drop_ui.placeholder = placeholder;
+        self
+    }
+
+    /// Draws the dragged item with `preview` instead of the item's content, e.g. to show a
+    /// semi-transparent card with the number of dragged items.
+    /// The ui has the size of the dragged item, see [`DragPreview`] for the available info.
+    #[must_use]
+    pub fn with_drag_preview(
+        mut self,
+        preview: impl Fn(&mut Ui, &DragPreview) + Send + Sync + 'static,
+    ) -> Self {
+        self.drag_drop_ui.drag_preview = Some(DragPreviewFn(Arc::new(preview)));
+        self
+    }
+
+    /// Moves an item as if it was dragged, e.g. to undo or redo a drag (see
+    /// [`DragUpdate::inverse`]).
+    /// The move is reported in [`DragDropResponse::update`], so [`DragDropResponse::update_vec`]
+    /// (and [`Dnd::show_vec`]) applies it, and the items animate to their new positions with the
+    /// same animation as while dragging.
+    /// Since it's not a finished drag, it doesn't show up in [`DragDropResponse::final_update`]
+    /// or [`DragDropResponse::final_gesture_update`].
+    /// The move is ignored if an item is being dragged.
+    #[must_use]
+    pub fn animate_move(mut self, update: DragUpdate) -> Self {
+        self.drag_drop_ui.animated_move = Some(update);
+        self
+    }
+
     /// Display the drag and drop UI.
     /// `items` should be an iterator over items that should be sortable.
     /// Each item needs to implement [`DragDropItem`]. This is automatically implement for every type that implements [`AsId`].
@@ -181,6 +337,42 @@ impl<'a> Dnd<'a> {
         })
     }
 
+    /// Display a tree, e.g. an outliner or a file tree.
+    /// `items` are the items of the tree together with their depth, in depth first order (each
+    /// item followed by its children). Each item is indented by its depth.
+    ///
+    /// Dragging an item horizontally changes the depth it's dropped at, so it can be moved to
+    /// another parent. The children of the dragged item are moved together with it, which
+    /// means an item can't be dropped into its own subtree.
+    /// [`DragDropResponse::tree_update`] contains the new parent and position of the item.
+    ///
+    /// The tree isn't updated automatically. Apply [`DragDropResponse::final_tree_update`] once
+    /// the item is dropped, e.g. with [`utils::shift_tree`] if you store the tree as a flat list.
+    /// Check the [tree example](https://github.com/lucasmerlin/hello_egui/blob/main/crates/egui_dnd/examples/tree.rs).
+    /// For a custom layout, use [`ItemIterator::next_in_tree`] with [`Dnd::show_custom`].
+    pub fn show_tree<T: DragDropItem>(
+        self,
+        items: impl Iterator<Item = (T, usize)>,
+        mut item_ui: impl FnMut(&mut Ui, T, Handle, ItemState),
+    ) -> DragDropResponse {
+        let indent = self.drag_drop_ui.tree_indent;
+        #[allow(clippy::used_underscore_items)]
+        self._show_with_inner(|_id, ui, drag_drop_ui| {
+            drag_drop_ui.ui(ui, |ui, iter| {
+                items.enumerate().for_each(|(i, (item, depth))| {
+                    iter.next_in_tree(ui, item.id(), i, depth, true, |ui, item_handle| {
+                        item_handle.ui(ui, |ui, handle, state| {
+                            ui.horizontal(|ui| {
+                                ui.add_space(indent * depth as f32);
+                                ui.vertical(|ui| item_ui(ui, item, handle, state));
+                            });
+                        })
+                    });
+                });
+            })
+        })
+    }
+
     /// Same as [`Dnd::show`], but automatically sorts the items.
     pub fn show_vec<T>(
         self,
@@ -1,13 +1,25 @@
 #[cfg(not(target_arch = "wasm32"))]
 use std::time::{Duration, SystemTime};
 
-use egui::{AsId, CursorIcon, Id, Pos2, Rect, Sense, Ui, Vec2};
+use egui::emath::easing;
+use egui::style::ScrollAnimation;
+use egui::{
+    AsId, CursorIcon, Direction, EventFilter, Id, Key, Modifiers, Pos2, Rect, Sense, StrokeKind,
+    Ui, Vec2, WidgetInfo, WidgetType,
+};
 
 #[cfg(target_arch = "wasm32")]
 use web_time::{Duration, SystemTime};
 
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+
+use crate::drop_target::{DropTargets, ExternalDrop};
+use crate::group::{AcceptFn, DndGroup, GroupDrag};
+use crate::item::DragPreviewFn;
 use crate::item_iterator::ItemIterator;
-use crate::utils::shift_vec;
+use crate::tree::tree_update;
+use crate::utils::{shift_block, shift_vec};
 
 /// Dragged item motion constraint
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -32,6 +44,19 @@ impl DragAxis {
     }
 }
 
+/// How the position the dragged item would be dropped at is shown,
+/// see [`crate::Dnd::with_insertion_indicator`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InsertionIndicator {
+    /// The items make room for the dragged item, opening a gap where it would be dropped (default).
+    #[default]
+    Gap,
+    /// A line is drawn between the items. The items stay in place while dragging.
+    Line,
+    /// Nothing is shown. The items stay in place while dragging.
+    None,
+}
+
 /// Item that can be reordered using drag and drop
 pub trait DragDropItem {
     /// Unique id for the item, to allow egui to keep track of its dragged state between frames
@@ -47,7 +72,7 @@ impl<T: AsId> DragDropItem for T {
 /// An instruction in what order to update the source list.
 /// The item at from should be removed from the list and inserted at to.
 /// You can use [`shift_vec`] to do this for a Vec.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct DragUpdate {
     /// Index of the item to move
     pub from: usize,
@@ -55,6 +80,114 @@ pub struct DragUpdate {
     pub to: usize,
 }
 
+impl DragUpdate {
+    /// The update that moves the item at `from`, so it ends up at index `target`
+    fn to_target(from: usize, target: usize) -> Self {
+        Self {
+            from,
+            to: if target > from { target + 1 } else { target },
+        }
+    }
+
+    /// The index the item ends up at, once the update was applied
+    fn target(&self) -> usize {
+        if self.to > self.from {
+            self.to - 1
+        } else {
+            self.to
+        }
+    }
+
+    /// Returns the update that reverts this one, e.g. to undo a drag.
+    #[must_use]
+    pub fn inverse(&self) -> Self {
+        Self::to_target(self.target(), self.from)
+    }
+}
+
+/// Decides whether a proposed update is allowed, see [`crate::Dnd::with_validation`]
+#[derive(Clone)]
+pub(crate) struct ValidateFn(pub Arc<dyn Fn(&DragUpdate) -> bool + Send + Sync>);
+
+impl Debug for ValidateFn {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ValidateFn")
+    }
+}
+
+/// Is moving the item at `from` to `to` allowed? The item can't be moved past a fixed item (see
+/// [`crate::Dnd::with_fixed_items`]) and the validation callback has to accept the update.
+fn allows_move(from: usize, to: usize, fixed: &[usize], validate: Option<&ValidateFn>) -> bool {
+    let update = DragUpdate { from, to };
+    let target = update.target();
+    let passed = from.min(target)..=from.max(target);
+    !fixed.iter().any(|idx| *idx != from && passed.contains(idx))
+        && validate.is_none_or(|validate| (validate.0)(&update))
+}
+
+/// Is moving the selected items at `from` as a block to `to` allowed? Each of them is checked
+/// with [`allows_move`], so none of them can be moved past a fixed item.
+fn allows_block_move(
+    from: &[usize],
+    to: usize,
+    fixed: &[usize],
+    validate: Option<&ValidateFn>,
+) -> bool {
+    from.iter()
+        .all(|from| allows_move(*from, to, fixed, validate))
+}
+
+/// An instruction to move an item from one list of a group to another, see [`crate::Dnd::with_group`].
+/// The item at `from` should be removed from the list `from_list` and inserted at `to` in the
+/// list `to_list`. You can use [`crate::utils::move_between_vecs`] to do this for two Vecs.
+#[derive(Debug, Clone)]
+pub struct CrossListUpdate {
+    /// Id of the list the item was dragged from (`Id::new(id_source)` of its [`crate::dnd`] call)
+    pub from_list: Id,
+    /// Index of the item in the list it was dragged from
+    pub from: usize,
+    /// Id of the list the item was dragged to (`Id::new(id_source)` of its [`crate::dnd`] call)
+    pub to_list: Id,
+    /// Where to insert the item in the list it was dragged to
+    pub to: usize,
+}
+
+/// An instruction to move a block of selected items, see [`crate::Dnd::with_selection`].
+/// The items at `from` should be removed from the list and inserted at `to`, keeping their
+/// relative order. You can use [`crate::utils::shift_block`] to do this for a Vec.
+#[derive(Debug, Clone)]
+pub struct MultiDragUpdate {
+    /// Indices of the items to move, in ascending order
+    pub from: Vec<usize>,
+    /// Where to move the items to (an index in the list before the items are removed)
+    pub to: usize,
+}
+
+/// Where an item of a tree was dropped, see [`crate::Dnd::show_tree`].
+/// The item (together with its children) should be moved to the position `index` among the
+/// children of `parent`.
+///
+/// For a tree stored as a flat list of items with their depth (like it's passed to
+/// [`crate::Dnd::show_tree`]), `from`, `len`, `to` and `depth` describe the same move, see
+/// [`crate::utils::shift_tree`].
+#[derive(Debug, Clone)]
+pub struct TreeUpdate {
+    /// Id of the dragged item
+    pub id: Id,
+    /// Flat index of the dragged item
+    pub from: usize,
+    /// Number of items that are moved: The dragged item and all its children
+    pub len: usize,
+    /// Flat index the items should be moved to (an index before the items are removed)
+    pub to: usize,
+    /// The new depth of the dragged item
+    pub depth: usize,
+    /// Id of the new parent, or `None` if the item is moved to the root level
+    pub parent: Option<Id>,
+    /// The position among the children of the new parent (not counting the dragged item)
+    pub index: usize,
+}
+
 /// Response containing state of the drag & drop list and a potential update to the source list.
 /// The update can be applied immediately or at latest when [`DragDropResponse::is_drag_finished`] returns true.
 #[derive(Debug, Clone)]
@@ -64,9 +197,27 @@ pub struct DragDropResponse {
     /// You can use this to consistently update the source list while the drag & drop event is ongoing.
     /// If you only want to update the source list when the drag & drop event has finished, use [`DragDropResponse::final_update`] instead.
     pub update: Option<DragUpdate>,
+    /// Set on the response of the list an item from another list of the group is dragged over
+    /// (see [`crate::Dnd::with_group`]). The list the item was dragged from reports no update
+    /// meanwhile. Apply it once [`DragDropResponse::is_drag_finished`] returns true, e.g. with
+    /// [`DragDropResponse::final_cross_list_update`].
+    pub cross_list_update: Option<CrossListUpdate>,
+    /// Set instead of [`DragDropResponse::update`] while multiple selected items are dragged
+    /// together, see [`crate::Dnd::with_selection`].
+    pub multi_update: Option<MultiDragUpdate>,
+    /// Set instead of [`DragDropResponse::update`] while an item of a tree is dragged, see
+    /// [`crate::Dnd::show_tree`]. Apply it once [`DragDropResponse::is_drag_finished`] returns
+    /// true, e.g. with [`DragDropResponse::final_tree_update`].
+    pub tree_update: Option<TreeUpdate>,
+    /// Set instead of [`DragDropResponse::update`] while the dragged item hovers a
+    /// [`crate::DropTarget`] of the list. Once [`DragDropResponse::is_drag_finished`] returns
+    /// true, the item was dropped on the target (see [`DragDropResponse::final_external_drop`]).
+    pub external_drop: Option<ExternalDrop>,
     finished: bool,
     cancellation_reason: Option<&'static str>,
     has_changed: bool,
+    /// The index the dragged item had when the drag started
+    gesture_from: Option<usize>,
 }
 
 impl DragDropResponse {
@@ -85,7 +236,7 @@ impl DragDropResponse {
     /// Returns the id of the item that is currently being dragged.
     #[must_use]
     pub fn dragged_item_id(&self) -> Option<Id> {
-        self.state.dragged_item()
+        self.state.dragged_item().or(self.state.keyboard_item())
     }
 
     /// Returns true if the drag & drop event has finished and the item has been dropped.
@@ -97,11 +248,16 @@ impl DragDropResponse {
 
     /// Utility function to update a Vec with the current drag & drop state.
     /// You can use this to consistently update the source list while the drag & drop event is ongoing.
+    /// When multiple selected items are dragged, the block move is applied, keeping the
+    /// relative order of the selected items.
     pub fn update_vec<T>(&self, vec: &mut [T]) {
         if self.has_changed || self.finished {
             if let Some(update) = &self.update {
                 shift_vec(update.from, update.to, vec);
             }
+            if let Some(update) = &self.multi_update {
+                shift_block(&update.from, update.to, vec);
+            }
         }
     }
 
@@ -116,6 +272,62 @@ impl DragDropResponse {
         }
     }
 
+    /// Returns the move of the whole drag once the item has been dropped, relative to the list
+    /// before the drag started.
+    /// When the list is updated while dragging (e.g. with [`DragDropResponse::update_vec`]),
+    /// a single drag results in many updates. This combines them into one, so each drag can be
+    /// stored as a single entry of an undo history. Use [`DragUpdate::inverse`] to revert it.
+    /// Returns `None` if the item was dropped where it was picked up.
+    #[must_use]
+    pub fn final_gesture_update(&self) -> Option<DragUpdate> {
+        let from = self.gesture_from?;
+        let target = self.final_update()?.target();
+        (target != from).then(|| DragUpdate::to_target(from, target))
+    }
+
+    /// Returns the block move if multiple selected items have been dropped.
+    /// Useful for the if let syntax.
+    #[must_use]
+    pub fn final_multi_update(&self) -> Option<MultiDragUpdate> {
+        if self.finished {
+            self.multi_update.clone()
+        } else {
+            None
+        }
+    }
+
+    /// Returns where an item of a tree has been dropped. Useful for the if let syntax.
+    #[must_use]
+    pub fn final_tree_update(&self) -> Option<TreeUpdate> {
+        if self.finished {
+            self.tree_update.clone()
+        } else {
+            None
+        }
+    }
+
+    /// Returns the target and item if an item has been dropped on a [`crate::DropTarget`].
+    /// Useful for the if let syntax.
+    #[must_use]
+    pub fn final_external_drop(&self) -> Option<ExternalDrop> {
+        if self.finished {
+            self.external_drop.clone()
+        } else {
+            None
+        }
+    }
+
+    /// Returns the cross list update if an item from another list of the group was dropped on
+    /// this list. Useful for the if let syntax.
+    #[must_use]
+    pub fn final_cross_list_update(&self) -> Option<CrossListUpdate> {
+        if self.finished {
+            self.cross_list_update.clone()
+        } else {
+            None
+        }
+    }
+
     /// Returns a [Option<&str>] with the reason if a drag & drop event was cancelled.
     #[must_use]
     pub fn cancellation_reason(&self) -> Option<&'static str> {
@@ -134,6 +346,35 @@ pub struct DragDropUi {
     pub(crate) swap_animation_time: f32,
     pub(crate) return_animation_time: f32,
     pub(crate) drag_axis: DragAxis,
+    /// `Id::new(id_source)` of the [`crate::dnd`] call
+    pub(crate) list_id: Id,
+    pub(crate) group: Option<Id>,
+    pub(crate) accept: Option<AcceptFn>,
+    /// Ids of the selected items, which are dragged together
+    pub(crate) selection: Vec<Id>,
+    /// The list an item is dragged from, while an item of another list of the group is dragged over this list
+    foreign_drag: Option<Id>,
+    /// The number of items shown in the last frame, used for keyboard reordering
+    item_count: usize,
+    /// How far an item of a tree has to be dragged horizontally to change its depth
+    pub(crate) tree_indent: f32,
+    pub(crate) insertion_indicator: InsertionIndicator,
+    /// Whether the original slot of the dragged item is kept and drawn as a placeholder
+    pub(crate) placeholder: bool,
+    /// Draws the dragged item instead of the item's content
+    pub(crate) drag_preview: Option<DragPreviewFn>,
+    /// Ids of the items that can't be dragged
+    pub(crate) locked: Vec<Id>,
+    /// Ids of the items that keep their position, other items can't be moved past them
+    pub(crate) fixed: Vec<Id>,
+    /// Indices of the fixed items shown in the last frame, used for keyboard reordering
+    fixed_indices: Vec<usize>,
+    pub(crate) validate: Option<ValidateFn>,
+    /// A move requested with [`crate::Dnd::animate_move`], reported in the next response
+    pub(crate) animated_move: Option<DragUpdate>,
+    /// Until when the items animate to their new positions after an animated move
+    move_animation_until: f64,
+    animating_move: bool,
 }
 
 impl Default for DragDropUi {
@@ -145,11 +386,33 @@ impl Default for DragDropUi {
             swap_animation_time: 0.2,
             return_animation_time: 0.2,
             drag_axis: DragAxis::Both,
+            list_id: Id::NULL,
+            group: None,
+            accept: None,
+            selection: Vec::new(),
+            foreign_drag: None,
+            item_count: 0,
+            tree_indent: 18.0,
+            insertion_indicator: InsertionIndicator::Gap,
+            placeholder: false,
+            drag_preview: None,
+            locked: Vec::new(),
+            fixed: Vec::new(),
+            fixed_indices: Vec::new(),
+            validate: None,
+            animated_move: None,
+            move_animation_until: 0.0,
+            animating_move: false,
         }
     }
 }
 
 /// [`Handle::ui`] is used to draw the drag handle
+///
+/// The handle can also be focused (e.g. with tab) to reorder the item with the keyboard:
+/// Space picks the item up, the arrow keys move it, space or enter drops it and escape cancels
+/// the drag. The moves are reported in [`DragDropResponse::update`] like a drag with the
+/// pointer, and the position is announced to screen readers.
 pub struct Handle<'a> {
     id: Id,
     idx: usize,
@@ -179,6 +442,9 @@ pub(crate) enum DragDetectionState {
     Dragging {
         id: Id,
         source_idx: usize,
+        /// The index the item was picked up at, the source index changes when the list is
+        /// updated while dragging
+        original_idx: usize,
         offset: Vec2,
         drag_start_pos: Pos2,
         dragged_item_size: Vec2,
@@ -193,6 +459,25 @@ pub(crate) enum DragDetectionState {
         id: Id,
         dragged_item_size: Option<Vec2>,
     },
+    /// An item was picked up with the keyboard, see [`Handle::ui`]
+    KeyboardDragging {
+        id: Id,
+        /// The current index of the item
+        source_idx: usize,
+        /// The index the item is moved to
+        target_idx: usize,
+        /// The index the item was picked up at, to move it back when the drag is cancelled
+        original_idx: usize,
+        action: KeyboardAction,
+    },
+}
+
+/// What happened during a keyboard drag in the current frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum KeyboardAction {
+    Move,
+    Drop,
+    Cancel,
 }
 
 impl DragDetectionState {
@@ -203,18 +488,28 @@ impl DragDetectionState {
     }
 
     pub(crate) fn is_dragging(&self) -> bool {
-        matches!(self, DragDetectionState::Dragging { .. })
+        matches!(
+            self,
+            DragDetectionState::Dragging { .. } | DragDetectionState::KeyboardDragging { .. }
+        )
     }
 
-    fn dragged_item(&self) -> Option<Id> {
+    pub(crate) fn dragged_item(&self) -> Option<Id> {
         match self {
             DragDetectionState::Dragging { id, .. } => Some(*id),
             _ => None,
         }
     }
 
+    fn keyboard_item(&self) -> Option<Id> {
+        match self {
+            DragDetectionState::KeyboardDragging { id, .. } => Some(*id),
+            _ => None,
+        }
+    }
+
     pub(crate) fn is_dragging_item(&self, id: Id) -> bool {
-        self.dragged_item() == Some(id)
+        self.dragged_item() == Some(id) || self.keyboard_item() == Some(id)
     }
 
     pub(crate) fn dragged_item_size(&self) -> Option<Vec2> {
@@ -230,6 +525,14 @@ impl DragDetectionState {
         }
     }
 
+    pub(crate) fn source_idx(&self) -> Option<usize> {
+        match self {
+            DragDetectionState::Dragging { source_idx, .. }
+            | DragDetectionState::KeyboardDragging { source_idx, .. } => Some(*source_idx),
+            _ => None,
+        }
+    }
+
     pub(crate) fn last_pointer_pos(&self) -> Option<Pos2> {
         match self {
             DragDetectionState::Dragging {
@@ -339,6 +642,11 @@ impl<'a> Handle<'a> {
         } else {
             response
         };
+        if self.state.locked.contains(&self.id) {
+            // Locked items can't be picked up, neither with the pointer nor the keyboard
+            return response;
+        }
+        self.keyboard_input(&response, ui);
 
         if response.contains_pointer() {
             if self.show_drag_cursor_on_hover {
@@ -397,6 +705,7 @@ impl<'a> Handle<'a> {
                 dragged_item_size: Vec2::default(),
                 closest_item: (self.id, self.item_pos),
                 source_idx: self.idx,
+                original_idx: self.idx,
                 hovering_idx: self.idx,
                 last_pointer_pos: ui
                     .ctx()
@@ -409,6 +718,143 @@ impl<'a> Handle<'a> {
 
         response
     }
+
+    /// Handle keyboard reordering: While the handle is focused, space picks the item up.
+    /// The arrow keys move it, space or enter drops it and escape cancels the drag.
+    /// The position is announced to screen readers.
+    fn keyboard_input(&mut self, handle_response: &egui::Response, ui: &mut Ui) {
+        // A separate widget, so the focus isn't lost when the handle response is recreated
+        let mut response = ui.interact(
+            handle_response.rect,
+            handle_response.id.with("egui_dnd_keyboard"),
+            Sense::focusable_noninteractive(),
+        );
+        let count = self.state.item_count.max(self.idx + 1);
+        let announcement = self.keyboard_action(&response, ui, count);
+
+        if response.has_focus() {
+            // Keep the focus while the arrow keys move the picked up item
+            let locked = self.state.detection_state.is_dragging_item(self.id);
+            ui.memory_mut(|mem| {
+                mem.set_focus_lock_filter(
+                    response.id,
+                    EventFilter {
+                        horizontal_arrows: locked,
+                        vertical_arrows: locked,
+                        escape: locked,
+                        ..EventFilter::default()
+                    },
+                );
+            });
+        }
+
+        if response.has_focus() {
+            ui.painter().rect_stroke(
+                response.rect,
+                ui.visuals().widgets.hovered.corner_radius,
+                ui.visuals().selection.stroke,
+                StrokeKind::Outside,
+            );
+        }
+
+        let label = if let Some(announcement) = announcement {
+            response.mark_changed();
+            announcement
+        } else if self.state.detection_state.is_dragging_item(self.id) {
+            "Picked up, use the arrow keys to move".to_owned()
+        } else {
+            format!(
+                "Drag handle, position {} of {count}. Press space to pick up",
+                self.idx + 1
+            )
+        };
+        response.widget_info(|| WidgetInfo::labeled(WidgetType::Other, true, &label));
+    }
+
+    /// Update the keyboard drag state, returns what should be announced to screen readers
+    fn keyboard_action(
+        &mut self,
+        response: &egui::Response,
+        ui: &Ui,
+        count: usize,
+    ) -> Option<String> {
+        match &mut self.state.detection_state {
+            DragDetectionState::KeyboardDragging {
+                id,
+                source_idx,
+                target_idx,
+                original_idx,
+                action,
+            } if *id == self.id => {
+                let (back, forward, drop, cancel) = ui.input_mut(|i| {
+                    (
+                        i.consume_key(Modifiers::NONE, Key::ArrowUp)
+                            | i.consume_key(Modifiers::NONE, Key::ArrowLeft),
+                        i.consume_key(Modifiers::NONE, Key::ArrowDown)
+                            | i.consume_key(Modifiers::NONE, Key::ArrowRight),
+                        i.consume_key(Modifiers::NONE, Key::Space)
+                            | i.consume_key(Modifiers::NONE, Key::Enter),
+                        i.consume_key(Modifiers::NONE, Key::Escape),
+                    )
+                });
+
+                let previous_idx = *target_idx;
+                if back {
+                    *target_idx = target_idx.saturating_sub(1);
+                }
+                if forward {
+                    *target_idx = (*target_idx + 1).min(count - 1);
+                }
+                let update = DragUpdate::to_target(*source_idx, *target_idx);
+                if !allows_move(
+                    update.from,
+                    update.to,
+                    &self.state.fixed_indices,
+                    self.state.validate.as_ref(),
+                ) {
+                    *target_idx = previous_idx;
+                }
+
+                if drop {
+                    *action = KeyboardAction::Drop;
+                    Some(format!(
+                        "Dropped at position {} of {count}",
+                        *target_idx + 1
+                    ))
+                } else if cancel || !response.has_focus() {
+                    *action = KeyboardAction::Cancel;
+                    Some(format!(
+                        "Reordering cancelled, back at position {} of {count}",
+                        *original_idx + 1
+                    ))
+                } else if *target_idx == previous_idx {
+                    None
+                } else {
+                    response.scroll_to_me(None);
+                    Some(format!("Moved to position {} of {count}", *target_idx + 1))
+                }
+            }
+            DragDetectionState::None
+            | DragDetectionState::TransitioningBackAfterDragFinished { .. }
+                if response.has_focus()
+                    && ui.input_mut(|i| i.consume_key(Modifiers::NONE, Key::Space)) =>
+            {
+                self.state.detection_state = DragDetectionState::KeyboardDragging {
+                    id: self.id,
+                    source_idx: self.idx,
+                    target_idx: self.idx,
+                    original_idx: self.idx,
+                    action: KeyboardAction::Move,
+                };
+                Some(format!(
+                    "Picked up at position {} of {count}. Use the arrow keys to move, \
+                    space to drop and escape to cancel",
+                    self.idx + 1
+                ))
+            }
+            _ => None,
+        }
+    }
 }
 
 /// Configuration for drag detection.
@@ -426,6 +872,17 @@ pub struct DragDropConfig {
     pub click_tolerance: f32,
     /// If we have been holding longer than this duration, a drag will be started even if the pointer has not moved above [`DragDropConfig::click_tolerance`].
     pub click_tolerance_timeout: Duration,
+    /// How close (in points) to the edge of the surrounding `ScrollArea` the pointer has to be
+    /// for the list to start scrolling while dragging. Set to 0 to disable auto-scrolling.
+    /// The default is 50 for [`DragDropConfig::mouse`] and 60 for the touch configs.
+    pub auto_scroll_edge: f32,
+    /// The scroll speed (in points per second) when the pointer is at or beyond the edge.
+    /// The default is 1000.
+    pub auto_scroll_speed: f32,
+    /// Maps how far the pointer is into the edge (0 at the inner border of the edge, 1 at the
+    /// edge itself) to a fraction of the [`DragDropConfig::auto_scroll_speed`].
+    /// The default is [`egui::emath::easing::quadratic_in`].
+    pub auto_scroll_curve: fn(f32) -> f32,
 }
 
 impl Default for DragDropConfig {
@@ -443,6 +900,9 @@ impl DragDropConfig {
             drag_delay: Duration::from_millis(0),
             scroll_tolerance: None,
             click_tolerance_timeout: Duration::from_millis(250),
+            auto_scroll_edge: 50.0,
+            auto_scroll_speed: 1000.0,
+            auto_scroll_curve: easing::quadratic_in,
         }
     }
 
@@ -455,6 +915,9 @@ impl DragDropConfig {
             click_tolerance: 3.0,
             drag_delay: Duration::from_millis(0),
             click_tolerance_timeout: Duration::from_millis(250),
+            auto_scroll_edge: 60.0,
+            auto_scroll_speed: 1000.0,
+            auto_scroll_curve: easing::quadratic_in,
         }
     }
 
@@ -466,6 +929,9 @@ impl DragDropConfig {
             click_tolerance: 3.0,
             drag_delay: Duration::from_millis(300),
             click_tolerance_timeout: Duration::from_millis(250),
+            auto_scroll_edge: 60.0,
+            auto_scroll_speed: 1000.0,
+            auto_scroll_curve: easing::quadratic_in,
         }
     }
 }
@@ -495,6 +961,144 @@ impl DragDropUi {
         self
     }
 
+    /// Is the dragged item one of multiple selected items?
+    pub(crate) fn is_multi_drag(&self) -> bool {
+        self.selection.len() > 1
+            && self
+                .detection_state
+                .dragged_item()
+                .is_some_and(|id| self.selection.contains(&id))
+    }
+
+    /// Is the item selected and carried along with the dragged item?
+    pub(crate) fn is_carried(&self, id: Id) -> bool {
+        self.is_multi_drag()
+            && !self.detection_state.is_dragging_item(id)
+            && self.selection.contains(&id)
+    }
+
+    /// Should the items animate to their new positions when the order changes?
+    pub(crate) fn animates_items(&self) -> bool {
+        self.detection_state.is_dragging() || self.animating_move
+    }
+
+    /// The number of items shown in the stacked preview of the dragged item
+    pub(crate) fn dragged_count(&self) -> usize {
+        if self.is_multi_drag() {
+            self.selection.len()
+        } else {
+            1
+        }
+    }
+
+    /// Start or stop showing the gap for an item that is dragged from another list of the group
+    fn sync_foreign_drag(&mut self, ui: &Ui, pointer_released: bool) {
+        let Some(group) = self.group else {
+            return;
+        };
+        let drag = DndGroup::with(ui.ctx(), group, |group| group.drag.clone())
+            .filter(|drag| drag.target_list == Some(self.list_id));
+
+        match drag {
+            Some(drag) if self.foreign_drag.is_some() || !self.detection_state.is_dragging() => {
+                let pointer_pos = ui.input(|i| i.pointer.hover_pos()).unwrap_or(drag.rect.min);
+                let (closest_item, hovering_idx, hovering_last_item) = match self.detection_state {
+                    DragDetectionState::Dragging {
+                        closest_item,
+                        hovering_idx,
+                        hovering_last_item,
+                        ..
+                    } if self.foreign_drag.is_some() => {
+                        (closest_item, hovering_idx, hovering_last_item)
+                    }
+                    _ => ((Id::NULL, drag.rect.min), 0, false),
+                };
+                self.foreign_drag = Some(drag.source_list);
+                self.detection_state = DragDetectionState::Dragging {
+                    id: drag.item,
+                    source_idx: drag.source_idx,
+                    original_idx: drag.source_idx,
+                    offset: drag.rect.min - pointer_pos,
+                    drag_start_pos: drag.rect.min,
+                    dragged_item_size: drag.rect.size(),
+                    closest_item,
+                    last_pointer_pos: pointer_pos,
+                    hovering_last_item,
+                    hovering_idx,
+                };
+            }
+            None if self.foreign_drag.is_some() && !pointer_released => {
+                self.foreign_drag = None;
+                self.detection_state =
+                    DragDetectionState::Cancelled("The dragged item left the list");
+            }
+            _ => {}
+        }
+    }
+
+    /// Register the list with its group and share the item dragged from this list.
+    /// Returns true if the dragged item hovers another list of the group.
+    fn sync_group(
+        &mut self,
+        ui: &Ui,
+        dragged_item_rect: Option<Rect>,
+        pointer_pos: Option<Pos2>,
+    ) -> bool {
+        let Some(group) = self.group else {
+            return false;
+        };
+        let frame_nr = ui.ctx().cumulative_frame_nr();
+        let rect = ui.min_rect().intersect(ui.clip_rect());
+        DndGroup::with(ui.ctx(), group, |group| {
+            group.register(frame_nr, self.list_id, rect, self.accept.clone());
+
+            if self.foreign_drag.is_some() {
+                return false;
+            }
+            if let (
+                DragDetectionState::Dragging { id, source_idx, .. },
+                Some(rect),
+                Some(pointer_pos),
+            ) = (&self.detection_state, dragged_item_rect, pointer_pos)
+            {
+                let target_list = group.target_list(frame_nr, self.list_id, pointer_pos);
+                group.drag = Some(GroupDrag {
+                    source_list: self.list_id,
+                    source_idx: *source_idx,
+                    item: *id,
+                    rect,
+                    target_list,
+                });
+                target_list.is_some()
+            } else {
+                if group
+                    .drag
+                    .as_ref()
+                    .is_some_and(|drag| drag.source_list == self.list_id)
+                {
+                    group.drag = None;
+                }
+                false
+            }
+        })
+    }
+
+    /// Find the [`crate::DropTarget`] of this list the dragged item is hovering
+    fn sync_drop_targets(&self, ui: &Ui, pointer_pos: Option<Pos2>) -> Option<Id> {
+        let frame_nr = ui.ctx().cumulative_frame_nr();
+        let dragged = match (&self.detection_state, pointer_pos) {
+            (DragDetectionState::Dragging { id, .. }, Some(pointer_pos))
+                if self.foreign_drag.is_none() =>
+            {
+                Some((*id, pointer_pos))
+            }
+            _ => None,
+        };
+        DropTargets::with(ui.ctx(), self.list_id, |targets| {
+            targets.update_hovered(frame_nr, dragged)
+        })
+    }
+
     fn config(&self, ui: &Ui) -> &DragDropConfig {
         if ui.input(egui::InputState::any_touches) {
             self.touch_config.as_ref().unwrap_or(&self.mouse_config)
@@ -503,6 +1107,70 @@ impl DragDropUi {
         }
     }
 
+    /// Scrolls the surrounding `ScrollArea` while the pointer is near or beyond one of its edges.
+    /// Only the viewport (the clip rect) is considered, so this also works for virtual lists that
+    /// only show the visible items.
+    fn auto_scroll(ui: &Ui, config: &DragDropConfig, pointer_pos: Pos2) {
+        let viewport = ui.clip_rect();
+        if config.auto_scroll_edge <= 0.0 || !viewport.is_positive() {
+            return;
+        }
+
+        let speed = |axis: usize| {
+            let (pos, min, max) = (pointer_pos[axis], viewport.min[axis], viewport.max[axis]);
+            // Don't scroll if the pointer is next to the viewport, e.g. over another list
+            let cross = 1 - axis;
+            if !(viewport.min[cross]..=viewport.max[cross]).contains(&pointer_pos[cross]) {
+                return 0.0;
+            }
+            // Keep the edges from overlapping in small viewports
+            let edge = config.auto_scroll_edge.min((max - min) / 3.0);
+            let depth = if pos < min + edge {
+                -(min + edge - pos) / edge
+            } else if pos > max - edge {
+                (pos - (max - edge)) / edge
+            } else {
+                return 0.0;
+            };
+            depth.signum()
+                * (config.auto_scroll_curve)(depth.abs().min(1.0))
+                * config.auto_scroll_speed
+        };
+
+        let velocity = Vec2::new(speed(0), speed(1));
+        if velocity != Vec2::ZERO {
+            let dt = ui.input(|i| i.stable_dt).min(0.1);
+            // A positive delta moves the content down, so we need to invert the velocity
+            ui.scroll_with_delta_animation(-velocity * dt, ScrollAnimation::none());
+            ui.ctx().request_repaint();
+        }
+    }
+
+    /// Draws the insertion line. In wrapping layouts it can be hard to tell which row the gap
+    /// belongs to, so we mark the slot with a line at the start of the gap there as well.
+    fn paint_slot_indicator(ui: &Ui, gap_rect: Rect) {
+        let spacing = ui.spacing().item_spacing / 2.0;
+        let stroke = ui.visuals().selection.stroke;
+        match ui.layout().main_dir {
+            Direction::LeftToRight => {
+                ui.painter()
+                    .vline(gap_rect.left() - spacing.x, gap_rect.y_range(), stroke);
+            }
+            Direction::RightToLeft => {
+                ui.painter()
+                    .vline(gap_rect.right() + spacing.x, gap_rect.y_range(), stroke);
+            }
+            Direction::TopDown => {
+                ui.painter()
+                    .hline(gap_rect.x_range(), gap_rect.top() - spacing.y, stroke);
+            }
+            Direction::BottomUp => {
+                ui.painter()
+                    .hline(gap_rect.x_range(), gap_rect.bottom() + spacing.y, stroke);
+            }
+        }
+    }
+
     /// Draw the items and handle drag & drop stuff
     #[allow(clippy::too_many_lines)] // TODO: refactor this to reduce the number of lines
     pub fn ui(
@@ -513,6 +1181,22 @@ impl DragDropUi {
         // During the first frame, we check if the pointer is actually over any of the item handles and cancel the drag if it isn't
         let mut first_frame = false;
         let config = self.config(ui).clone();
+        let pointer_released = ui.input(|i| i.pointer.any_released());
+
+        self.sync_foreign_drag(ui, pointer_released);
+
+        let animated_move = self
+            .animated_move
+            .take()
+            .filter(|_| !self.detection_state.is_dragging());
+        let time = ui.input(|i| i.time);
+        if animated_move.is_some() {
+            // The items only move in the next frame, once the update was applied
+            let dt = ui.input(|i| i.stable_dt);
+            self.move_animation_until = time + f64::from(self.swap_animation_time + dt * 2.0);
+            ui.ctx().request_repaint();
+        }
+        self.animating_move = time < self.move_animation_until;
 
         ui.input(|i| {
             if i.pointer.any_down() {
@@ -586,6 +1270,11 @@ impl DragDropUi {
 
         let ItemIterator {
             source_item,
+            item_count,
+            selected_indices,
+            fixed_indices,
+            tree,
+            gap_rect,
             hovering_over_any_handle,
             mut closest_item,
             mark_next_as_closest_item,
@@ -604,7 +1293,6 @@ impl DragDropUi {
             false
         };
 
-        let pointer_released = ui.input(|i| i.pointer.any_released());
         let should_update = closest_item.is_some_and(|i| i.1.is_some());
 
         // The cursor is not hovering over any item, so cancel
@@ -615,7 +1303,33 @@ impl DragDropUi {
 
         let drag_phase_changed_this_frame = false;
 
-        let hovering_item = closest_item.and_then(|i| i.1);
+        // Keep the gap where it is if the item can't be moved to the hovered position
+        let source_idx = source_item
+            .map(|item| item.0)
+            .or(self.detection_state.source_idx());
+        let hovering_item = closest_item.and_then(|i| i.1).filter(|(hovering_idx, ..)| {
+            let to = if hovering_last_item {
+                hovering_idx + 1
+            } else {
+                *hovering_idx
+            };
+            match source_idx {
+                // The fixed items don't apply to items dragged in from another list of the group
+                Some(from) if self.foreign_drag.is_some() => {
+                    allows_move(from, to, &[], self.validate.as_ref())
+                }
+                Some(_) if self.is_multi_drag() && !selected_indices.is_empty() => {
+                    allows_block_move(
+                        &selected_indices,
+                        to,
+                        &fixed_indices,
+                        self.validate.as_ref(),
+                    )
+                }
+                Some(from) => allows_move(from, to, &fixed_indices, self.validate.as_ref()),
+                None => true,
+            }
+        });
 
         if let DragDetectionState::Dragging {
             closest_item: closest_out,
@@ -639,10 +1353,36 @@ impl DragDropUi {
             }
         }
 
-        if self.detection_state.is_dragging() {
+        self.item_count = item_count;
+        self.fixed_indices = fixed_indices;
+        if let DragDetectionState::KeyboardDragging { source_idx, .. } = &mut self.detection_state {
+            if let Some(source_item) = source_item {
+                *source_idx = source_item.0;
+            }
+        }
+
+        let hovering_other_list = self.sync_group(ui, dragged_item_rect, pointer_pos);
+        let external_target = self.sync_drop_targets(ui, pointer_pos);
+        if hovering_other_list || external_target.is_some() {
+            // Close the gap, the item is shown in the other list or dropped on the target
+            if let DragDetectionState::Dragging {
+                closest_item,
+                hovering_last_item,
+                ..
+            } = &mut self.detection_state
+            {
+                *closest_item = (Id::NULL, closest_item.1);
+                *hovering_last_item = false;
+            }
+        }
+
+        if matches!(self.detection_state, DragDetectionState::Dragging { .. })
+            && !hovering_other_list
+            && external_target.is_none()
+        {
             if let Some(pointer_pos) = pointer_pos {
                 // If we are in a ScrollArea, allow for scrolling while dragging
-                ui.scroll_to_rect(Rect::from_center_size(pointer_pos, Vec2::splat(50.0)), None);
+                Self::auto_scroll(ui, &config, pointer_pos);
             }
         }
 
@@ -650,38 +1390,142 @@ impl DragDropUi {
             DragDropResponse {
                 finished: false,
                 update: None,
+                cross_list_update: None,
+                multi_update: None,
+                tree_update: None,
+                external_drop: None,
                 state: self.detection_state.clone(),
                 cancellation_reason: None,
                 has_changed: false,
+                gesture_from: None,
+            }
+        } else if let DragDetectionState::KeyboardDragging {
+            source_idx,
+            target_idx,
+            original_idx,
+            action,
+            ..
+        } = self.detection_state
+        {
+            let target_idx = if action == KeyboardAction::Cancel {
+                original_idx
+            } else {
+                target_idx
+            };
+            if action != KeyboardAction::Move {
+                self.detection_state = DragDetectionState::None;
+            }
+            DragDropResponse {
+                finished: action == KeyboardAction::Drop,
+                update: Some(DragUpdate {
+                    from: source_idx,
+                    to: if target_idx > source_idx {
+                        target_idx + 1
+                    } else {
+                        target_idx
+                    },
+                }),
+                cross_list_update: None,
+                multi_update: None,
+                tree_update: None,
+                external_drop: None,
+                state: self.detection_state.clone(),
+                cancellation_reason: (action == KeyboardAction::Cancel)
+                    .then_some("The keyboard drag was cancelled"),
+                has_changed: source_idx != target_idx,
+                gesture_from: Some(original_idx),
             }
         } else if let DragDetectionState::Dragging {
+            id,
             source_idx,
+            original_idx,
             hovering_idx,
             hovering_last_item,
+            drag_start_pos,
             ..
         } = self.detection_state
         {
+            let to = if hovering_last_item {
+                hovering_idx + 1
+            } else {
+                hovering_idx
+            };
+            let reorder =
+                self.foreign_drag.is_none() && !hovering_other_list && external_target.is_none();
+            // The selected items aren't known yet if the drag started during this frame
+            let multi_drag = self.is_multi_drag() && !selected_indices.is_empty();
+            // Gathering the selected items can already move them past a fixed item, in that case
+            // they stay where they are until the block is dragged to an allowed position
+            let block_allowed = multi_drag
+                && allows_block_move(
+                    &selected_indices,
+                    to,
+                    &self.fixed_indices,
+                    self.validate.as_ref(),
+                );
+            let tree_update = dragged_item_rect.filter(|_| reorder).and_then(|rect| {
+                let depth_steps = (rect.min.x - drag_start_pos.x) / self.tree_indent;
+                tree_update(&tree, id, to, depth_steps)
+            });
+            if let (Some(update), Some(gap_rect)) = (&tree_update, gap_rect) {
+                // Show the depth the item would be dropped at
+                let x = gap_rect.left() + self.tree_indent * update.depth as f32;
+                ui.painter().hline(
+                    x..=gap_rect.right(),
+                    gap_rect.center().y,
+                    ui.visuals().selection.stroke,
+                );
+            } else if let Some(gap_rect) = gap_rect {
+                match self.insertion_indicator {
+                    InsertionIndicator::Line => Self::paint_slot_indicator(ui, gap_rect),
+                    InsertionIndicator::Gap if ui.layout().main_wrap => {
+                        Self::paint_slot_indicator(ui, gap_rect);
+                    }
+                    _ => {}
+                }
+            }
             DragDropResponse {
                 finished: false,
-                update: Some(DragUpdate {
+                update: (reorder && !multi_drag && tree.is_empty()).then_some(DragUpdate {
+                    from: source_idx,
+                    to,
+                }),
+                multi_update: (reorder && block_allowed).then_some(MultiDragUpdate {
+                    from: selected_indices,
+                    to,
+                }),
+                cross_list_update: self.foreign_drag.map(|from_list| CrossListUpdate {
+                    from_list,
+                    from: source_idx,
+                    to_list: self.list_id,
+                    to,
+                }),
+                tree_update,
+                external_drop: external_target.map(|target| ExternalDrop {
+                    target,
+                    item: id,
                     from: source_idx,
-                    to: if hovering_last_item {
-                        hovering_idx + 1
-                    } else {
-                        hovering_idx
-                    },
                 }),
                 state: self.detection_state.clone(),
                 cancellation_reason: None,
-                has_changed: should_update,
+                // Without a gap the items stay in place, so the update is only applied on drop
+                has_changed: should_update
+                    && reorder
+                    && self.insertion_indicator == InsertionIndicator::Gap,
+                gesture_from: Some(original_idx),
             }
         } else {
             DragDropResponse {
                 finished: false,
                 update: None,
+                cross_list_update: None,
+                multi_update: None,
+                tree_update: None,
+                external_drop: None,
                 state: self.detection_state.clone(),
                 cancellation_reason: None,
                 has_changed: false,
+                gesture_from: None,
             }
         };
 
@@ -689,9 +1533,21 @@ impl DragDropUi {
             if let Some(dragged_item) = self.detection_state.dragged_item() {
                 response.finished = true;
 
-                self.detection_state = DragDetectionState::TransitioningBackAfterDragFinished {
-                    dragged_item_size: self.detection_state.dragged_item_size(),
-                    id: dragged_item,
+                self.detection_state = if self.foreign_drag.take().is_some() {
+                    // The item is dropped into this list, it's shown by the list it came from until then
+                    DragDetectionState::None
+                } else if let Some(drop) = &response.external_drop {
+                    DropTargets::with(ui.ctx(), self.list_id, |targets| {
+                        targets.drop(drop.target, drop.item);
+                    });
+                    // The target might have been shown before the list
+                    ui.ctx().request_repaint();
+                    DragDetectionState::None
+                } else {
+                    DragDetectionState::TransitioningBackAfterDragFinished {
+                        dragged_item_size: self.detection_state.dragged_item_size(),
+                        id: dragged_item,
+                    }
                 };
             }
         }
@@ -701,6 +1557,7 @@ impl DragDropUi {
                 && !matches!(
                     self.detection_state,
                     DragDetectionState::TransitioningBackAfterDragFinished { .. }
+                        | DragDetectionState::KeyboardDragging { .. }
                 )
             {
                 if let DragDetectionState::Cancelled(msg) = self.detection_state {
@@ -720,6 +1577,63 @@ impl DragDropUi {
             ui.ctx().request_repaint();
         }
 
+        if let Some(update) = animated_move {
+            response.update = Some(update);
+            response.has_changed = true;
+        }
+
         response
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inverse_restores_order() {
+        let original: Vec<usize> = (0..5).collect();
+        for (from, to) in [(0, 3), (1, 5), (4, 0), (3, 1), (2, 2), (2, 3)] {
+            let update = DragUpdate { from, to };
+            let mut vec = original.clone();
+            shift_vec(update.from, update.to, &mut vec);
+            let inverse = update.inverse();
+            shift_vec(inverse.from, inverse.to, &mut vec);
+            assert_eq!(vec, original, "{update:?} reverted with {inverse:?}");
+        }
+    }
+
+    #[test]
+    fn fixed_items() {
+        // Moves across a fixed item are rejected
+        assert!(!allows_move(0, 4, &[2], None));
+        assert!(!allows_move(4, 1, &[2], None));
+        assert!(!allows_move(1, 3, &[2], None));
+        // Moves that don't reach the fixed item are allowed
+        assert!(allows_move(3, 5, &[2], None));
+        assert!(allows_move(1, 0, &[2], None));
+        // Moves between two fixed items are allowed
+        assert!(allows_move(1, 4, &[0, 4], None));
+        assert!(allows_move(3, 1, &[0, 4], None));
+        assert!(!allows_move(1, 5, &[0, 4], None));
+    }
+
+    #[test]
+    fn fixed_items_block_move() {
+        assert!(!allows_block_move(&[0, 1], 4, &[2], None));
+        assert!(!allows_block_move(&[0, 3], 1, &[2], None));
+        assert!(allows_block_move(&[0, 1], 2, &[2], None));
+        assert!(allows_block_move(&[1, 3], 2, &[0, 4], None));
+    }
+
+    #[test]
+    fn validation() {
+        let validate = ValidateFn(Arc::new(|update: &DragUpdate| update.to != 0));
+        assert!(!allows_move(2, 0, &[], Some(&validate)));
+        assert!(allows_move(2, 1, &[], Some(&validate)));
+        // Both the fixed items and the validation callback have to allow the move
+        assert!(!allows_move(2, 4, &[3], Some(&validate)));
+        assert!(!allows_block_move(&[1, 2], 0, &[], Some(&validate)));
+        assert!(allows_block_move(&[1, 2], 4, &[], Some(&validate)));
+    }
+}
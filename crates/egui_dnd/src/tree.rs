@@ -0,0 +1,164 @@
+use egui::Id;
+
+use crate::TreeUpdate;
+
+/// An item shown with [`crate::ItemIterator::next_in_tree`]
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct TreeEntry {
+    pub idx: usize,
+    pub id: Id,
+    pub depth: usize,
+    /// Set for the children of the dragged item, which are moved together with it
+    pub hidden: bool,
+}
+
+/// Find where the dragged item would be dropped, when it's inserted at the flat index `to`.
+/// `depth_steps` is how many indentation levels the item was dragged to the right (or left, if negative).
+/// The depth is limited so the item stays a valid child of the item above it, and since the
+/// children of the dragged item are hidden, it can't be dropped into its own subtree.
+pub(crate) fn tree_update(
+    tree: &[TreeEntry],
+    dragged: Id,
+    to: usize,
+    depth_steps: f32,
+) -> Option<TreeUpdate> {
+    let source_pos = tree.iter().position(|entry| entry.id == dragged)?;
+    let source = tree[source_pos];
+    let len = 1 + tree[source_pos + 1..]
+        .iter()
+        .take_while(|entry| entry.hidden)
+        .count();
+
+    let mut visible = tree
+        .iter()
+        .filter(|entry| !entry.hidden && entry.id != dragged);
+    let before: Vec<&TreeEntry> = visible
+        .by_ref()
+        .take_while(|entry| entry.idx < to)
+        .collect();
+    let next = tree
+        .iter()
+        .find(|entry| !entry.hidden && entry.id != dragged && entry.idx >= to);
+
+    let max_depth = before.last().map_or(0, |prev| prev.depth + 1);
+    let min_depth = next.map_or(0, |next| next.depth).min(max_depth);
+    let depth = (source.depth as f32 + depth_steps.round())
+        .max(0.0)
+        .clamp(min_depth as f32, max_depth as f32) as usize;
+
+    let parent = before.iter().rposition(|entry| entry.depth < depth);
+    let index = before[parent.map_or(0, |parent| parent + 1)..]
+        .iter()
+        .filter(|entry| entry.depth == depth)
+        .count();
+
+    Some(TreeUpdate {
+        id: dragged,
+        from: source.idx,
+        len,
+        to,
+        depth,
+        parent: parent.map(|parent| before[parent].id),
+        index,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::shift_tree;
+
+    /// The entries of `tree` while `dragged` is dragged, its children are hidden
+    fn entries(tree: &[(&str, usize)], dragged: &str) -> Vec<TreeEntry> {
+        let mut dragged_depth = None;
+        tree.iter()
+            .enumerate()
+            .map(|(idx, (name, depth))| {
+                let hidden = dragged_depth.is_some_and(|dragged_depth| *depth > dragged_depth);
+                if !hidden {
+                    dragged_depth = (*name == dragged).then_some(*depth);
+                }
+                TreeEntry {
+                    idx,
+                    id: Id::new(name),
+                    depth: *depth,
+                    hidden,
+                }
+            })
+            .collect()
+    }
+
+    fn drop(
+        tree: &mut Vec<(&str, usize)>,
+        dragged: &str,
+        to: usize,
+        depth_steps: f32,
+    ) -> TreeUpdate {
+        let update =
+            tree_update(&entries(tree, dragged), Id::new(dragged), to, depth_steps).unwrap();
+        shift_tree(&update, tree);
+        update
+    }
+
+    const TREE: [(&str, usize); 5] = [("a", 0), ("a1", 1), ("b", 0), ("b1", 1), ("c", 0)];
+
+    #[test]
+    fn nest() {
+        let mut tree = TREE.to_vec();
+        let update = drop(&mut tree, "c", 4, 1.0);
+        assert_eq!(
+            (update.depth, update.parent, update.index),
+            (1, Some(Id::new("b")), 1)
+        );
+        assert_eq!(tree, [("a", 0), ("a1", 1), ("b", 0), ("b1", 1), ("c", 1)]);
+
+        // The depth is limited to one level below the item above
+        let mut tree = TREE.to_vec();
+        let update = drop(&mut tree, "c", 4, 3.0);
+        assert_eq!(
+            (update.depth, update.parent, update.index),
+            (2, Some(Id::new("b1")), 0)
+        );
+        assert_eq!(tree, [("a", 0), ("a1", 1), ("b", 0), ("b1", 1), ("c", 2)]);
+
+        // Dropped above the child of an item, the item has to become a child as well
+        let mut tree = TREE.to_vec();
+        let update = drop(&mut tree, "c", 3, -1.0);
+        assert_eq!(
+            (update.depth, update.parent, update.index),
+            (1, Some(Id::new("b")), 0)
+        );
+        assert_eq!(tree, [("a", 0), ("a1", 1), ("b", 0), ("c", 1), ("b1", 1)]);
+    }
+
+    #[test]
+    fn unnest() {
+        let mut tree = TREE.to_vec();
+        let update = drop(&mut tree, "b1", 4, -1.0);
+        assert_eq!((update.depth, update.parent, update.index), (0, None, 2));
+        assert_eq!(tree, [("a", 0), ("a1", 1), ("b", 0), ("b1", 0), ("c", 0)]);
+
+        let mut tree = TREE.to_vec();
+        let update = drop(&mut tree, "a1", 1, -1.0);
+        assert_eq!((update.depth, update.parent, update.index), (0, None, 1));
+        assert_eq!(tree, [("a", 0), ("a1", 0), ("b", 0), ("b1", 1), ("c", 0)]);
+    }
+
+    #[test]
+    fn move_with_children_to_end() {
+        let mut tree = TREE.to_vec();
+        let update = drop(&mut tree, "a", 5, 0.0);
+        assert_eq!((update.from, update.len, update.to), (0, 2, 5));
+        assert_eq!((update.depth, update.parent, update.index), (0, None, 2));
+        assert_eq!(tree, [("b", 0), ("b1", 1), ("c", 0), ("a", 0), ("a1", 1)]);
+
+        // Nested below the last item, the children keep their depth relative to the item
+        let mut tree = TREE.to_vec();
+        let update = drop(&mut tree, "a", 5, 1.0);
+        assert_eq!(
+            (update.depth, update.parent, update.index),
+            (1, Some(Id::new("c")), 0)
+        );
+        assert_eq!(tree, [("b", 0), ("b1", 1), ("c", 0), ("a", 1), ("a1", 2)]);
+    }
+}
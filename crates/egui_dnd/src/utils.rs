@@ -41,3 +41,103 @@ pub fn shift_vec<T>(source_idx: usize, target_idx: usize, vec: &mut [T]) {
         );
     }
 }
+
+/// Move a block of items in a slice according to the drag and drop logic, e.g. to apply a
+/// [`crate::MultiDragUpdate`].
+///
+/// The items at `source_indices` (in ascending order) end up next to each other, in their
+/// previous relative order, at the position of `target_idx`. Like with [`shift_vec`],
+/// `target_idx` is an index in the slice before the items are moved.
+///
+/// # Example
+///
+/// ```rust
+/// use egui_dnd::utils::shift_block;
+///
+/// let mut v = vec![1, 2, 3, 4, 5];
+/// shift_block(&[0, 3], 2, &mut v);
+/// assert_eq!(v, [2, 1, 4, 3, 5]);
+/// shift_block(&[1, 2], 5, &mut v);
+/// assert_eq!(v, [2, 3, 5, 1, 4]);
+/// ```
+///
+/// # Panics
+/// Panics if any index in `source_indices` is `>= len()` or `target_idx > len()`
+pub fn shift_block<T>(source_indices: &[usize], target_idx: usize, vec: &mut [T]) {
+    // Items before the target are moved in front of the target, starting with the last one
+    let mut block_start = target_idx;
+    for &source_idx in source_indices.iter().rev().filter(|idx| **idx < target_idx) {
+        shift_vec(source_idx, block_start, vec);
+        block_start -= 1;
+    }
+    // Items after the target are moved behind the block, starting with the first one
+    let after_target = source_indices.iter().filter(|idx| **idx >= target_idx);
+    for (block_end, &source_idx) in (target_idx..).zip(after_target) {
+        shift_vec(source_idx, block_end, vec);
+    }
+}
+
+/// Apply a [`crate::TreeUpdate`] to a tree stored as a flat list of items with their depth,
+/// in depth first order (like it's passed to [`crate::Dnd::show_tree`]).
+/// Moves the dragged item together with its children and updates their depth.
+///
+/// # Example
+///
+/// ```rust
+/// use egui::Id;
+/// use egui_dnd::utils::shift_tree;
+/// use egui_dnd::TreeUpdate;
+///
+/// let mut tree = vec![("a", 0), ("a1", 1), ("b", 0), ("b1", 1), ("b2", 1)];
+/// // Move b (with its children) into a
+/// let update = TreeUpdate {
+///     id: Id::new("b"),
+///     from: 2,
+///     len: 3,
+///     to: 2,
+///     depth: 1,
+///     parent: Some(Id::new("a")),
+///     index: 1,
+/// };
+/// shift_tree(&update, &mut tree);
+/// assert_eq!(tree, [("a", 0), ("a1", 1), ("b", 1), ("b1", 2), ("b2", 2)]);
+/// ```
+///
+/// # Panics
+/// Panics if the update doesn't fit the list
+pub fn shift_tree<T>(update: &crate::TreeUpdate, items: &mut [(T, usize)]) {
+    let moved = update.from..update.from + update.len;
+    let old_depth = items[update.from].1;
+    for (_, depth) in &mut items[moved.clone()] {
+        *depth = *depth - old_depth + update.depth;
+    }
+    shift_block(&moved.collect::<Vec<_>>(), update.to, items);
+}
+
+/// Move an item from one Vec to another, e.g. to apply a [`crate::CrossListUpdate`].
+///
+/// Removes the item at `source_idx` from `source` and inserts it at `target_idx` in `target`.
+///
+/// # Example
+///
+/// ```rust
+/// use egui_dnd::utils::move_between_vecs;
+///
+/// let mut todo = vec![1, 2, 3];
+/// let mut done = vec![4, 5];
+/// move_between_vecs(1, 1, &mut todo, &mut done);
+/// assert_eq!(todo, [1, 3]);
+/// assert_eq!(done, [4, 2, 5]);
+/// ```
+///
+/// # Panics
+/// Panics if `source_idx >= source.len()` or `target_idx > target.len()`
+pub fn move_between_vecs<T>(
+    source_idx: usize,
+    target_idx: usize,
+    source: &mut Vec<T>,
+    target: &mut Vec<T>,
+) {
+    let item = source.remove(source_idx);
+    target.insert(target_idx, item);
+}
@@ -0,0 +1,110 @@
+use egui::{Event, Id, Modifiers, PointerButton, Pos2, Rect, Vec2};
+use egui_dnd::utils::move_between_vecs;
+use egui_dnd::{dnd, CrossListUpdate};
+use egui_kittest::Harness;
+use std::collections::HashMap;
+
+#[derive(Default)]
+struct App {
+    lists: Vec<(&'static str, Vec<&'static str>)>,
+    /// Where the items were shown before they were dragged
+    rects: HashMap<&'static str, Rect>,
+    /// The cross list updates reported once an item was dropped
+    updates: Vec<CrossListUpdate>,
+}
+
+fn harness(lists: Vec<(&'static str, Vec<&'static str>)>) -> Harness<'static, App> {
+    Harness::builder().with_step_dt(0.1).build_ui_state(
+        |ui, state: &mut App| {
+            let App {
+                lists,
+                rects,
+                updates,
+            } = state;
+            ui.columns(lists.len(), |uis| {
+                for (ui, (name, items)) in uis.iter_mut().zip(lists.iter_mut()) {
+                    let response = dnd(ui, *name).with_group("group").show_vec(
+                        items,
+                        |ui, item, handle, _state| {
+                            let response = handle.ui(ui, |ui| {
+                                ui.label(*item);
+                            });
+                            rects.entry(*item).or_insert(response.rect);
+                        },
+                    );
+                    updates.extend(response.final_cross_list_update());
+                }
+            });
+        },
+        App {
+            lists,
+            ..App::default()
+        },
+    )
+}
+
+/// Press the pointer at `from`, move it to `to` and release it there
+fn drag(harness: &mut Harness<'_, App>, from: Pos2, to: Pos2) {
+    let button = |pos, pressed| Event::PointerButton {
+        pos,
+        button: PointerButton::Primary,
+        pressed,
+        modifiers: Modifiers::default(),
+    };
+    harness.input_mut().events.push(Event::PointerMoved(from));
+    harness.input_mut().events.push(button(from, true));
+    harness.step();
+    // The drag starts once the pointer moved while it's still on the item
+    harness
+        .input_mut()
+        .events
+        .push(Event::PointerMoved(from + Vec2::X * 5.0));
+    harness.step();
+    for step in 1..=5_u8 {
+        let pos = from.lerp(to, f32::from(step) / 5.0);
+        harness.input_mut().events.push(Event::PointerMoved(pos));
+        harness.step();
+    }
+    harness.input_mut().events.push(button(to, false));
+    // Let the item animate to its new position
+    harness.run_steps(10);
+}
+
+#[test]
+fn cross_list_drag() {
+    let mut harness = harness(vec![("a", vec!["a1", "a2", "a3"]), ("b", vec!["b1", "b2"])]);
+    let from = harness.state().rects["a2"].center();
+    let b2 = harness.state().rects["b2"];
+    // The upper half of b2, so the item is inserted in front of it
+    drag(&mut harness, from, b2.center_top() + Vec2::Y * 2.0);
+
+    let [update] = harness.state().updates.as_slice() else {
+        panic!("expected one update, got {:?}", harness.state().updates);
+    };
+    assert_eq!(
+        (update.from_list, update.from, update.to_list, update.to),
+        (Id::new("a"), 1, Id::new("b"), 1)
+    );
+
+    let update = update.clone();
+    let state = harness.state_mut();
+    let (a, b) = state.lists.split_at_mut(1);
+    move_between_vecs(update.from, update.to, &mut a[0].1, &mut b[0].1);
+    assert_eq!(state.lists[0].1, ["a1", "a3"]);
+    assert_eq!(state.lists[1].1, ["b1", "a2", "b2"]);
+
+    // And back to the first list, in front of a3
+    harness.state_mut().rects.clear();
+    harness.state_mut().updates.clear();
+    harness.run();
+    let from = harness.state().rects["b1"].center();
+    let a3 = harness.state().rects["a3"];
+    drag(&mut harness, from, a3.center_top() + Vec2::Y * 2.0);
+    let [update] = harness.state().updates.as_slice() else {
+        panic!("expected one update, got {:?}", harness.state().updates);
+    };
+    assert_eq!(
+        (update.from_list, update.from, update.to_list, update.to),
+        (Id::new("b"), 0, Id::new("a"), 1)
+    );
+}
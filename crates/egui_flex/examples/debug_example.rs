@@ -1,13 +1,25 @@
 use eframe::NativeOptions;
 use eframe::emath::Align2;
 use egui::{Area, Frame, Id, Vec2};
-use egui_flex::{Flex, FlexItem};
+use egui_flex::{Flex, FlexItem, LayoutCacheStats};
 
 fn main() -> eframe::Result {
     eframe::run_ui_native(
         "flex debug example",
         NativeOptions::default(),
         |ui, _frame| {
+            // Stats of the previous frame, reset so we see the hits and misses per frame
+            let stats = LayoutCacheStats::get(ui.ctx());
+            LayoutCacheStats::reset(ui.ctx());
+            Area::new(Id::new("stats"))
+                .anchor(egui::Align2::LEFT_BOTTOM, [0.0, 0.0])
+                .show(ui.ctx(), |ui| {
+                    ui.label(format!(
+                        "Layout cache hits: {}, misses: {}",
+                        stats.hits, stats.misses
+                    ));
+                });
+
             Area::new(Id::new("area"))
                 .anchor(egui::Align2::LEFT_TOP, [0.0, 0.0])
                 .show(ui.ctx(), |ui| {
@@ -0,0 +1,72 @@
+use eframe::NativeOptions;
+use egui::{Button, CentralPanel, Frame, Label, ProgressBar, Slider, TextEdit};
+use egui_flex::{Flex, FlexAlign, FlexGrid, GridTrack, item};
+
+fn main() -> eframe::Result {
+    let mut name = "Ferris".to_string();
+    let mut email = "ferris@example.com".to_string();
+    let mut volume = 0.5;
+
+    eframe::run_ui_native(file!(), NativeOptions::default(), move |ui, _frame| {
+        CentralPanel::default().show(ui, |ui| {
+            let frame = Frame::group(ui.style());
+
+            // A dashboard: a sidebar spanning all rows, cards in the remaining columns
+            FlexGrid::new()
+                .columns([
+                    GridTrack::MinMax(120.0, 200.0),
+                    GridTrack::Fraction(1.0),
+                    GridTrack::Fraction(1.0),
+                ])
+                .w_full()
+                .show(ui, |grid| {
+                    grid.add_flex(
+                        item().row_span(3).frame(frame),
+                        Flex::vertical().w_full(),
+                        |flex| {
+                            flex.add(item(), Button::new("Overview"));
+                            flex.add(item(), Button::new("Settings"));
+                            flex.add(item(), Button::new("Logs"));
+                        },
+                    );
+
+                    grid.add_ui(item().column_span(2).frame(frame), |ui| {
+                        ui.heading("Welcome back!");
+                    });
+
+                    for idx in 0..4 {
+                        grid.add_ui(item().frame(frame), |ui| {
+                            ui.label(format!("Card {idx}"));
+                            ui.add(ProgressBar::new(idx as f32 / 4.0));
+                        });
+                    }
+                });
+
+            ui.separator();
+
+            // A form with aligned label and value columns
+            FlexGrid::new()
+                .columns([GridTrack::Auto, GridTrack::Fraction(1.0)])
+                .align_items(FlexAlign::Center)
+                .w_full()
+                .show(ui, |grid| {
+                    grid.add(item(), Label::new("Name"));
+                    grid.add(
+                        item().align_self(FlexAlign::Stretch),
+                        TextEdit::singleline(&mut name),
+                    );
+                    grid.add(item(), Label::new("Email address"));
+                    grid.add(
+                        item().align_self(FlexAlign::Stretch),
+                        TextEdit::singleline(&mut email),
+                    );
+                    grid.add(item(), Label::new("Volume"));
+                    grid.add(item().grid_column(1), Slider::new(&mut volume, 0.0..=1.0));
+                    grid.add(
+                        item().grid_column(1).align_self(FlexAlign::End),
+                        Button::new("Save"),
+                    );
+                });
+        });
+    })
+}
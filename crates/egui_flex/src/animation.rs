@@ -0,0 +1,356 @@
+use egui::emath::{TSTransform, easing};
+use egui::epaint::shape_transform::adjust_colors;
+use egui::epaint::{ClippedShape, Shape};
+use egui::layers::{PaintList, ShapeIdx};
+use egui::util::IdTypeMap;
+use egui::{Color32, Id, IdMap, Painter, Pos2, Rect, Ui, lerp};
+use std::sync::Arc;
+
+/// Opt-in animation of the items in a [`crate::Flex`] container, see [`crate::Flex::animate`].
+///
+/// When the layout changes (e.g. because an item was added, removed or resized), every item is
+/// moved and resized from its old to its new rect with an eased interpolation. The items are laid
+/// out in their new rect right away and only painted with a transform, so their size doesn't
+/// change while the animation runs. Since the transform can only scale uniformly, a resized item
+/// is scaled to fit its animated rect.
+///
+/// Items are matched between frames by their [`crate::FlexItem::content_id`] or, if it isn't set,
+/// by the order they are added in (hidden items included), so set a content id on items that can
+/// be added or removed.
+///
+/// New items fade in. Items that are hidden (see [`crate::FlexItem::hidden`]) fade out at their
+/// last position, while the other items move to close the gap. Items that aren't added anymore
+/// fade out as well, but since their content can't be shown anymore, the shapes they painted in
+/// their last frame are faded out instead.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FlexAnimation {
+    duration: f32,
+    enter: bool,
+    exit: bool,
+}
+
+impl Default for FlexAnimation {
+    fn default() -> Self {
+        Self {
+            duration: 0.25,
+            enter: true,
+            exit: true,
+        }
+    }
+}
+
+impl FlexAnimation {
+    /// Create a new animation config.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// How long an animation takes, in seconds.
+    ///
+    /// Default is 0.25.
+    pub fn duration(mut self, duration: f32) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    /// Should new items fade in?
+    ///
+    /// Default is true.
+    pub fn enter(mut self, enter: bool) -> Self {
+        self.enter = enter;
+        self
+    }
+
+    /// Should hidden and removed items fade out?
+    ///
+    /// To fade out removed items, the shapes of every item are copied each frame, so disable this
+    /// if the items paint a lot.
+    ///
+    /// Default is true.
+    pub fn exit(mut self, exit: bool) -> Self {
+        self.exit = exit;
+        self
+    }
+
+    fn progress(self, start_time: f64, time: f64) -> f32 {
+        if self.duration > 0.0 {
+            (((time - start_time) as f32) / self.duration).clamp(0.0, 1.0)
+        } else {
+            1.0
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ItemAnimation {
+    source: Rect,
+    target: Rect,
+    start_time: f64,
+    enter_time: Option<f64>,
+    exit_time: Option<f64>,
+    used: bool,
+    ghost: Option<Ghost>,
+}
+
+/// What an item painted in its last frame, faded out if the item isn't added anymore
+#[derive(Debug, Clone)]
+struct Ghost {
+    shapes: Arc<[ClippedShape]>,
+    // The container origin when the shapes were painted
+    origin: Pos2,
+    // The animated rect the shapes were painted in, relative to the origin
+    rect: Rect,
+}
+
+impl ItemAnimation {
+    fn rect(&self, animation: FlexAnimation, time: f64) -> Rect {
+        let t = easing::cubic_out(animation.progress(self.start_time, time));
+        self.source.lerp_towards(&self.target, t)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct AnimationState {
+    items: IdMap<ItemAnimation>,
+    // Was the container shown in a previous frame? Otherwise all items are new and shouldn't
+    // fade in.
+    shown: bool,
+    // The frame the state was stored in
+    frame_nr: u64,
+}
+
+/// Where and how to show an animated item.
+pub(crate) struct AnimatedItem {
+    pub rect: Rect,
+    pub opacity: f32,
+    pub scale: f32,
+}
+
+impl AnimatedItem {
+    /// A transform moving an item that is laid out in `layout_rect` to the animated rect, scaled
+    /// to fit it.
+    pub fn transform(&self, layout_rect: Rect) -> TSTransform {
+        let fit = (self.rect.size() / layout_rect.size()).min_elem();
+        let fit = if fit.is_finite() && fit > 0.0 {
+            fit
+        } else {
+            1.0
+        };
+        TSTransform::from_translation(self.rect.center().to_vec2())
+            * TSTransform::from_scaling(fit * self.scale)
+            * TSTransform::from_translation(-layout_rect.center().to_vec2())
+    }
+}
+
+/// The index the next shape painted in the ui's layer will get.
+pub(crate) fn next_shape_idx(ui: &Ui) -> ShapeIdx {
+    ui.ctx().graphics(|graphics| {
+        graphics
+            .get(ui.layer_id())
+            .map_or(ShapeIdx(0), PaintList::next_idx)
+    })
+}
+
+/// The animation state of a flex container during a pass.
+pub(crate) struct FlexAnimator {
+    animation: FlexAnimation,
+    id: Id,
+    state: AnimationState,
+    // The rects are stored relative to the container origin, so scrolling doesn't animate them
+    origin: Pos2,
+    time: f64,
+    frame_nr: u64,
+    animating: bool,
+    // The index of the next item, used as key for items without a content id
+    next_index: usize,
+    // Reserved before the items are shown, so removed items are painted below them
+    ghost_slot: Option<ShapeIdx>,
+}
+
+impl FlexAnimator {
+    pub fn load(ui: &Ui, id: Id, animation: FlexAnimation, origin: Pos2) -> Self {
+        let id = id.with("animation");
+        let mut state: AnimationState = ui.data(|data| data.get_temp(id).unwrap_or_default());
+        let frame_nr = ui.ctx().cumulative_frame_nr();
+        // Don't fade out items that were removed while the container wasn't shown
+        if state.frame_nr + 1 < frame_nr {
+            for item in state.items.values_mut() {
+                item.ghost = None;
+            }
+        }
+        Self {
+            animation,
+            id,
+            state,
+            origin,
+            time: ui.input(|i| i.time),
+            frame_nr,
+            animating: false,
+            next_index: 0,
+            ghost_slot: animation.exit.then(|| ui.painter().add(Shape::Noop)),
+        }
+    }
+
+    /// The key an item is matched by between frames: its content id or, if it isn't set, the
+    /// order it is added in. Has to be called for every item that isn't absolute, in order.
+    pub fn key(&mut self, content_id: Option<Id>) -> Id {
+        let index = self.next_index;
+        self.next_index += 1;
+        content_id.unwrap_or_else(|| self.id.with(index))
+    }
+
+    /// Animate an item towards its rect in the new layout.
+    pub fn item(&mut self, key: Id, target: Rect) -> AnimatedItem {
+        let target = target.translate(-self.origin.to_vec2());
+        let time = self.time;
+        let enter = (self.animation.enter && self.state.shown).then_some(time);
+        let item = self
+            .state
+            .items
+            .entry(key)
+            .or_insert_with(|| ItemAnimation {
+                source: target,
+                target,
+                start_time: time,
+                enter_time: enter,
+                exit_time: None,
+                used: false,
+                ghost: None,
+            });
+
+        // The item was hidden and is shown again
+        if item.exit_time.is_some() {
+            *item = ItemAnimation {
+                source: target,
+                target,
+                start_time: time,
+                enter_time: self.animation.enter.then_some(time),
+                exit_time: None,
+                used: false,
+                ghost: None,
+            };
+        }
+
+        if item.target != target {
+            item.source = item.rect(self.animation, time);
+            item.target = target;
+            item.start_time = time;
+        }
+        item.used = true;
+
+        let move_progress = self.animation.progress(item.start_time, time);
+        let enter_progress = item
+            .enter_time
+            .map_or(1.0, |enter_time| self.animation.progress(enter_time, time));
+        if (move_progress < 1.0 && item.source != item.target) || enter_progress < 1.0 {
+            self.animating = true;
+        }
+
+        let enter = easing::cubic_out(enter_progress);
+        AnimatedItem {
+            rect: item
+                .rect(self.animation, time)
+                .translate(self.origin.to_vec2()),
+            opacity: enter,
+            scale: lerp(0.9..=1.0, enter),
+        }
+    }
+
+    /// Animate a hidden item out. Returns `None` once the item is gone.
+    pub fn exit(&mut self, key: Id) -> Option<AnimatedItem> {
+        if !self.animation.exit {
+            return None;
+        }
+        let time = self.time;
+        let item = self.state.items.get_mut(&key)?;
+        let exit_time = *item.exit_time.get_or_insert(time);
+        let progress = self.animation.progress(exit_time, time);
+        if progress >= 1.0 {
+            return None;
+        }
+        item.used = true;
+        self.animating = true;
+
+        let exit = easing::cubic_out(progress);
+        Some(AnimatedItem {
+            rect: item
+                .rect(self.animation, time)
+                .translate(self.origin.to_vec2()),
+            opacity: 1.0 - exit,
+            scale: lerp(1.0..=0.9, exit),
+        })
+    }
+
+    /// Remember the shapes an item painted since `start`, so they can be faded out if the item
+    /// isn't added anymore.
+    pub fn capture(&mut self, ui: &Ui, key: Id, start: ShapeIdx) {
+        if !self.animation.exit {
+            return;
+        }
+        let Some(item) = self.state.items.get_mut(&key) else {
+            return;
+        };
+        let shapes = ui.ctx().graphics(|graphics| {
+            graphics.get(ui.layer_id()).map_or_else(Vec::new, |list| {
+                list.all_entries().skip(start.0).cloned().collect()
+            })
+        });
+        item.ghost = Some(Ghost {
+            shapes: shapes.into(),
+            origin: self.origin,
+            rect: item.rect(self.animation, self.time),
+        });
+    }
+
+    /// Fade out the items that weren't added in this pass, using the shapes they painted last.
+    /// Has to be called after all items were added.
+    pub fn paint_removed(&mut self, painter: &Painter) {
+        let Some(slot) = self.ghost_slot else {
+            return;
+        };
+        let time = self.time;
+        let mut shapes = Vec::new();
+        for item in self.state.items.values_mut() {
+            let Some(ghost) = item.ghost.as_ref().filter(|_| !item.used) else {
+                continue;
+            };
+            let exit_time = *item.exit_time.get_or_insert(time);
+            let progress = self.animation.progress(exit_time, time);
+            if progress >= 1.0 {
+                continue;
+            }
+            item.used = true;
+            self.animating = true;
+
+            let exit = easing::cubic_out(progress);
+            let opacity = 1.0 - exit;
+            let center = (ghost.rect.center() + self.origin.to_vec2()).to_vec2();
+            let transform = TSTransform::from_translation(center)
+                * TSTransform::from_scaling(lerp(1.0..=0.9, exit))
+                * TSTransform::from_translation(self.origin - ghost.origin - center);
+            shapes.extend(ghost.shapes.iter().map(|clipped| {
+                let mut shape = clipped.shape.clone();
+                shape.transform(transform);
+                adjust_colors(&mut shape, move |color| {
+                    if *color != Color32::PLACEHOLDER {
+                        *color = color.gamma_multiply(opacity);
+                    }
+                });
+                shape
+            }));
+        }
+        painter.set(slot, Shape::Vec(shapes));
+    }
+
+    /// Store the state for the next pass, forgetting items that weren't shown. Returns whether
+    /// an animation is still running.
+    pub fn store(mut self, data: &mut IdTypeMap, shown: bool) -> bool {
+        self.state
+            .items
+            .retain(|_, item| std::mem::take(&mut item.used));
+        self.state.shown = shown;
+        self.state.frame_nr = self.frame_nr;
+        data.insert_temp(self.id, self.state);
+        self.animating
+    }
+}
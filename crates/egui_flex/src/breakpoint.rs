@@ -0,0 +1,183 @@
+use crate::{Flex, FlexDirection, FlexItem};
+use egui::{Vec2, vec2};
+
+/// A condition on the available size of a flex container, used to configure containers and
+/// items differently depending on how much space there is (e.g. on a phone vs. on a desktop).
+///
+/// A breakpoint matches if the available size is at least its min size and below its max size.
+/// Like css media queries, the overrides of all matching breakpoints are applied in the order
+/// they were added, so later overrides win.
+///
+/// ```
+/// # use egui_flex::{Breakpoint, Flex, FlexDirection, FlexOverrides};
+/// // Stack the items on small screens and put them in a row on larger ones
+/// let flex = Flex::vertical().at(
+///     Breakpoint::MD,
+///     FlexOverrides::new().direction(FlexDirection::Horizontal),
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Breakpoint {
+    min: Vec2,
+    max: Vec2,
+}
+
+impl Breakpoint {
+    /// Small screens, matches an available width of at least 640.
+    pub const SM: Self = Self::min_width(640.0);
+    /// Medium screens, matches an available width of at least 768.
+    pub const MD: Self = Self::min_width(768.0);
+    /// Large screens, matches an available width of at least 1024.
+    pub const LG: Self = Self::min_width(1024.0);
+    /// Extra large screens, matches an available width of at least 1280.
+    pub const XL: Self = Self::min_width(1280.0);
+
+    /// Matches if the available width is at least `width`.
+    pub const fn min_width(width: f32) -> Self {
+        Self {
+            min: vec2(width, 0.0),
+            max: Vec2::INFINITY,
+        }
+    }
+
+    /// Matches if the available width is less than `width`.
+    pub const fn max_width(width: f32) -> Self {
+        Self {
+            min: Vec2::ZERO,
+            max: vec2(width, f32::INFINITY),
+        }
+    }
+
+    /// Matches if the available height is at least `height`.
+    pub const fn min_height(height: f32) -> Self {
+        Self {
+            min: vec2(0.0, height),
+            max: Vec2::INFINITY,
+        }
+    }
+
+    /// Matches if the available height is less than `height`.
+    pub const fn max_height(height: f32) -> Self {
+        Self {
+            min: Vec2::ZERO,
+            max: vec2(f32::INFINITY, height),
+        }
+    }
+
+    /// Matches if both breakpoints match.
+    pub fn and(self, other: Self) -> Self {
+        Self {
+            min: self.min.max(other.min),
+            max: self.max.min(other.max),
+        }
+    }
+
+    /// Does this breakpoint match the available size?
+    pub fn matches(&self, available_size: Vec2) -> bool {
+        available_size.x >= self.min.x
+            && available_size.y >= self.min.y
+            && available_size.x < self.max.x
+            && available_size.y < self.max.y
+    }
+}
+
+impl From<f32> for Breakpoint {
+    fn from(min_width: f32) -> Self {
+        Self::min_width(min_width)
+    }
+}
+
+/// Overrides for the configuration of a [`Flex`] container, applied when a [`Breakpoint`]
+/// matches. See [`Flex::at`].
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FlexOverrides {
+    direction: Option<FlexDirection>,
+    wrap: Option<bool>,
+    gap: Option<Vec2>,
+}
+
+impl FlexOverrides {
+    /// Create new overrides that don't change anything yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Override the direction. See [`Flex::direction`].
+    pub fn direction(mut self, direction: FlexDirection) -> Self {
+        self.direction = Some(direction);
+        self
+    }
+
+    /// Override whether the container wraps. See [`Flex::wrap`].
+    pub fn wrap(mut self, wrap: bool) -> Self {
+        self.wrap = Some(wrap);
+        self
+    }
+
+    /// Override the gap. See [`Flex::gap`].
+    pub fn gap(mut self, gap: Vec2) -> Self {
+        self.gap = Some(gap);
+        self
+    }
+}
+
+/// Overrides for the configuration of a [`FlexItem`], applied when a [`Breakpoint`] matches the
+/// available size of the container. See [`FlexItem::at`].
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FlexItemOverrides {
+    grow: Option<f32>,
+    basis: Option<f32>,
+    hidden: Option<bool>,
+}
+
+impl FlexItemOverrides {
+    /// Create new overrides that don't change anything yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Override the grow factor. See [`FlexItem::grow`].
+    pub fn grow(mut self, grow: f32) -> Self {
+        self.grow = Some(grow);
+        self
+    }
+
+    /// Override the basis. See [`FlexItem::basis`].
+    pub fn basis(mut self, basis: f32) -> Self {
+        self.basis = Some(basis);
+        self
+    }
+
+    /// Hide or show the item. See [`FlexItem::hidden`].
+    pub fn hidden(mut self, hidden: bool) -> Self {
+        self.hidden = Some(hidden);
+        self
+    }
+}
+
+impl Flex {
+    /// Apply the overrides of all breakpoints that match the available size.
+    pub(crate) fn resolve_breakpoints(mut self, available_size: Vec2) -> Self {
+        for (breakpoint, overrides) in std::mem::take(&mut self.breakpoints) {
+            if breakpoint.matches(available_size) {
+                self.direction = overrides.direction.unwrap_or(self.direction);
+                self.wrap = overrides.wrap.unwrap_or(self.wrap);
+                self.gap = overrides.gap.or(self.gap);
+            }
+        }
+        self
+    }
+}
+
+impl FlexItem<'_> {
+    /// Apply the overrides of all breakpoints that match the available size of the container.
+    pub(crate) fn resolve_breakpoints(&mut self, available_size: Vec2) {
+        for (breakpoint, overrides) in std::mem::take(&mut self.breakpoints) {
+            if breakpoint.matches(available_size) {
+                self.inner.grow = overrides.grow.or(self.inner.grow);
+                self.inner.basis = overrides.basis.or(self.inner.basis);
+                self.inner.hidden = overrides.hidden.unwrap_or(self.inner.hidden);
+            }
+        }
+    }
+}
@@ -0,0 +1,720 @@
+use crate::{
+    ContentFn, Flex, FlexAlign, FlexInstance, FlexItem, FlexItemInner, ItemState, Size,
+    add_positioned,
+};
+use egui::emath::GuiRounding;
+use egui::{Align2, Id, InnerResponse, Pos2, Rect, Response, Sense, Ui, UiBuilder, Vec2, Widget};
+
+/// The size of a column or row in a [`FlexGrid`].
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum GridTrack {
+    /// Size in points (pixels).
+    Points(f32),
+    /// A fraction of the space that is left after all other tracks have been sized, like the
+    /// css `fr` unit. The track will never be smaller than its content.
+    ///
+    /// If the grid has no explicit size in this direction, this behaves like [`GridTrack::Auto`].
+    Fraction(f32),
+    /// Size the track to fit its largest item.
+    #[default]
+    Auto,
+    /// Size the track to fit its content, but at least `min` and at most `max` points.
+    ///
+    /// If the grid has an explicit size in this direction, the track grows up to `max` to fill
+    /// the remaining space.
+    MinMax(f32, f32),
+}
+
+impl From<f32> for GridTrack {
+    fn from(p: f32) -> Self {
+        GridTrack::Points(p)
+    }
+}
+
+/// A grid container, laying out [`FlexItem`]s in aligned columns and rows.
+///
+/// Items are placed in the next free cell (row by row) unless [`FlexItem::grid_column`] or
+/// [`FlexItem::grid_row`] is set, and can span multiple tracks via [`FlexItem::column_span`] and
+/// [`FlexItem::row_span`]. Rows that are not defined via [`Self::rows`] are added as needed and
+/// sized according to [`Self::auto_rows`].
+///
+/// Like [`Flex`], the grid remembers the size of its items from the previous frame and requests a
+/// discard if they change.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct FlexGrid {
+    id_salt: Option<Id>,
+    // Columns and rows, indexed by axis
+    tracks: [Vec<GridTrack>; 2],
+    auto_rows: GridTrack,
+    gap: Option<Vec2>,
+    default_item: FlexItemInner,
+    width: Option<Size>,
+    height: Option<Size>,
+}
+
+impl FlexGrid {
+    /// Create a new grid container.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the column definitions.
+    ///
+    /// Default is a single [`GridTrack::Auto`] column.
+    pub fn columns(mut self, columns: impl IntoIterator<Item = GridTrack>) -> Self {
+        self.tracks[0] = columns.into_iter().collect();
+        self
+    }
+
+    /// Set the row definitions. Additional rows are sized according to [`Self::auto_rows`].
+    pub fn rows(mut self, rows: impl IntoIterator<Item = GridTrack>) -> Self {
+        self.tracks[1] = rows.into_iter().collect();
+        self
+    }
+
+    /// Set the size of rows that aren't defined via [`Self::rows`].
+    ///
+    /// Default is [`GridTrack::Auto`].
+    pub fn auto_rows(mut self, track: GridTrack) -> Self {
+        self.auto_rows = track;
+        self
+    }
+
+    /// Set the gap between the columns and rows.
+    ///
+    /// Default is `item_spacing` of the [`Ui`].
+    pub fn gap(mut self, gap: Vec2) -> Self {
+        self.gap = Some(gap);
+        self
+    }
+
+    /// How should items be aligned within their cell?
+    ///
+    /// Default is `stretch`.
+    pub fn align_items(mut self, align_items: FlexAlign) -> Self {
+        self.default_item.align_self = Some(align_items);
+        self
+    }
+
+    /// If `align_items` is stretch, how do we align the item content?
+    pub fn align_items_content(mut self, align_item_content: Align2) -> Self {
+        self.default_item.align_content = Some(align_item_content);
+        self
+    }
+
+    /// Customize the id of the grid container to prevent conflicts with other containers.
+    pub fn id_salt(mut self, id_salt: impl Into<Id>) -> Self {
+        self.id_salt = Some(id_salt.into());
+        self
+    }
+
+    /// Set the width of the grid container. [`GridTrack::Fraction`] columns will grow to fill it.
+    ///
+    /// The default depends on the parents horizontal justify, see [`Flex::width`].
+    pub fn width(mut self, width: impl Into<Size>) -> Self {
+        self.width = Some(width.into());
+        self
+    }
+
+    /// Set the height of the grid container. [`GridTrack::Fraction`] rows will grow to fill it.
+    ///
+    /// The default depends on the parents vertical justify, see [`Flex::height`].
+    pub fn height(mut self, height: impl Into<Size>) -> Self {
+        self.height = Some(height.into());
+        self
+    }
+
+    /// Set the size of the grid container in points (pixels).
+    pub fn size(mut self, size: impl Into<Vec2>) -> Self {
+        let size = size.into();
+        self.width = Some(Size::Points(size.x));
+        self.height = Some(Size::Points(size.y));
+        self
+    }
+
+    /// Set the width of the grid container to 100%.
+    pub fn w_full(mut self) -> Self {
+        self.width = Some(Size::Percent(1.0));
+        self
+    }
+
+    /// Set the height of the grid container to 100%.
+    pub fn h_full(mut self) -> Self {
+        self.height = Some(Size::Percent(1.0));
+        self
+    }
+
+    fn track(&self, axis: usize, index: usize) -> GridTrack {
+        self.tracks[axis]
+            .get(index)
+            .copied()
+            .unwrap_or(if axis == 0 {
+                GridTrack::Auto
+            } else {
+                self.auto_rows
+            })
+    }
+
+    fn column_count(&self) -> usize {
+        self.tracks[0].len().max(1)
+    }
+
+    #[track_caller]
+    #[allow(clippy::too_many_lines)]
+    pub(crate) fn show_inside<R>(
+        self,
+        ui: &mut Ui,
+        target_size: Option<Vec2>,
+        max_item_size: Option<Vec2>,
+        f: impl FnOnce(&mut FlexGridInstance) -> R,
+    ) -> (Vec2, GridState, InnerResponse<R>) {
+        let id = if let Some(id_salt) = self.id_salt {
+            ui.id().with(id_salt)
+        } else {
+            ui.auto_id_with("flex_grid")
+        };
+        let previous_state: GridState = ui
+            .ctx()
+            .memory(|mem| mem.data.get_temp(id).clone().unwrap_or_default());
+
+        let frame_time = ui.ctx().input(|i| i.time);
+        let passes = ui.ctx().cumulative_pass_nr();
+        if cfg!(debug_assertions)
+            && (previous_state.frame_time == frame_time && previous_state.passes == passes)
+        {
+            panic!("Id clash in flex grid! Id: {id:?}");
+        }
+
+        let width = self.width.or_else(|| {
+            if ui.layout().horizontal_justify() {
+                Some(Size::Percent(1.0))
+            } else {
+                None
+            }
+        });
+        let height = self.height.or_else(|| {
+            if ui.layout().vertical_justify() {
+                Some(Size::Percent(1.0))
+            } else {
+                None
+            }
+        });
+
+        let mut state_changed = false;
+
+        let parent_rect = ui.max_rect();
+
+        let r = ui.scope_builder(
+            UiBuilder::new().max_rect(ui.available_rect_before_wrap().round_ui()),
+            |ui| {
+                let gap = self.gap.unwrap_or(ui.spacing().item_spacing);
+
+                let available_size = target_size.unwrap_or(parent_rect.size());
+                let size_origin = Vec2::max(
+                    target_size.unwrap_or(parent_rect.size()),
+                    parent_rect.size(),
+                );
+
+                let size = [
+                    width.map(|w| w.get(size_origin.x).round_ui()),
+                    height.map(|h| h.get(size_origin.y).round_ui()),
+                ];
+
+                let origin = ui.min_rect().min;
+                let tracks = [0, 1].map(|axis| {
+                    self.layout_tracks(&previous_state.items, axis, size[axis], gap, origin)
+                });
+
+                let max_item_size = max_item_size.unwrap_or(available_size).round();
+
+                let mut instance = FlexGridInstance {
+                    grid: &self,
+                    previous_items: &previous_state.items,
+                    state: GridState {
+                        items: vec![],
+                        max_item_size,
+                        frame_time,
+                        passes,
+                    },
+                    ui,
+                    tracks,
+                    cursor: [0, 0],
+                    max_item_size,
+                };
+
+                let r = f(&mut instance);
+
+                let mut min_size = Vec2::ZERO;
+                let mut grid_size = Vec2::ZERO;
+                for axis in 0..2 {
+                    let min_tracks = self.size_tracks(&instance.state.items, axis, None, gap[axis]);
+                    min_size[axis] = min_tracks.iter().sum::<f32>()
+                        + gap[axis] * (min_tracks.len().max(1) - 1) as f32;
+                    grid_size[axis] = size[axis].unwrap_or(instance.tracks[axis].length());
+                }
+                min_size = min_size.min(max_item_size);
+
+                if (&previous_state.items, &previous_state.max_item_size)
+                    != (&instance.state.items, &instance.state.max_item_size)
+                {
+                    state_changed = true;
+                }
+
+                instance.ui.ctx().memory_mut(|mem| {
+                    mem.data.insert_temp(id, instance.state.clone());
+                });
+
+                instance
+                    .ui
+                    .allocate_rect(Rect::from_min_size(origin, grid_size), Sense::hover());
+
+                (min_size, instance.state, r)
+            },
+        );
+
+        // We move this down here because `#[track_caller]` doesn't work with closures
+        if state_changed {
+            ui.ctx()
+                .request_discard("Grid item added / removed / size changed");
+            ui.ctx().request_repaint();
+        }
+
+        (
+            r.inner.0,
+            r.inner.1,
+            InnerResponse::new(r.inner.2, r.response),
+        )
+    }
+
+    fn layout_tracks(
+        &self,
+        items: &[GridItemState],
+        axis: usize,
+        available: Option<f32>,
+        gap: Vec2,
+        origin: Pos2,
+    ) -> Tracks {
+        let sizes = self.size_tracks(items, axis, available, gap[axis]);
+
+        let mut offsets = Vec::with_capacity(sizes.len());
+        let mut position = origin[axis];
+        for size in &sizes {
+            offsets.push(position);
+            position += size + gap[axis];
+        }
+
+        Tracks {
+            offsets,
+            sizes,
+            gap: gap[axis],
+            origin: origin[axis],
+        }
+    }
+
+    /// Calculate the track sizes along an axis, based on the items from the previous frame.
+    /// If `available` is `None`, fraction and min max tracks don't grow, giving the min size.
+    fn size_tracks(
+        &self,
+        items: &[GridItemState],
+        axis: usize,
+        available: Option<f32>,
+        gap: f32,
+    ) -> Vec<f32> {
+        let count = if axis == 0 {
+            self.column_count()
+        } else {
+            items
+                .iter()
+                .map(|item| item.area.start[1] + item.area.span[1])
+                .max()
+                .unwrap_or(0)
+                .max(self.tracks[1].len())
+        };
+
+        let mut sizes: Vec<f32> = (0..count)
+            .map(|index| match self.track(axis, index) {
+                GridTrack::Points(points) => points,
+                GridTrack::MinMax(min, _) => min,
+                GridTrack::Fraction(_) | GridTrack::Auto => 0.0,
+            })
+            .collect();
+        let is_intrinsic = |index| !matches!(self.track(axis, index), GridTrack::Points(_));
+
+        // Items spanning a single track size that track directly
+        for item in items.iter().filter(|item| item.area.span[axis] == 1) {
+            let index = item.area.start[axis];
+            if index < count && is_intrinsic(index) {
+                sizes[index] = f32::max(sizes[index], item.item.min_size_with_margin()[axis]);
+            }
+        }
+
+        // Spanning items distribute the space they're missing across their intrinsic tracks,
+        // smaller spans first
+        let mut spanning: Vec<_> = items
+            .iter()
+            .filter(|item| item.area.span[axis] > 1)
+            .collect();
+        spanning.sort_by_key(|item| item.area.span[axis]);
+        for item in spanning {
+            let range = item.area.start[axis]..(item.area.start[axis] + item.area.span[axis]);
+            let range = range.start.min(count)..range.end.min(count);
+            let current =
+                sizes[range.clone()].iter().sum::<f32>() + gap * (range.len().max(1) - 1) as f32;
+            let missing = item.item.min_size_with_margin()[axis] - current;
+            let growable: Vec<_> = range.filter(|index| is_intrinsic(*index)).collect();
+            if missing > 0.0 && !growable.is_empty() {
+                for index in &growable {
+                    sizes[*index] += missing / growable.len() as f32;
+                }
+            }
+        }
+
+        for (index, size) in sizes.iter_mut().enumerate() {
+            if let GridTrack::MinMax(min, max) = self.track(axis, index) {
+                *size = size.min(max).max(min);
+            }
+        }
+
+        if let Some(available) = available {
+            let available = available - gap * (count.max(1) - 1) as f32;
+
+            // Grow the min max tracks up to their max size. Every pass either freezes a track at
+            // its max or uses up the free space, so we need at most count + 1 passes.
+            let mut growing: Vec<_> = (0..count)
+                .filter(|index| matches!(self.track(axis, *index), GridTrack::MinMax(..)))
+                .collect();
+            for _ in 0..=count {
+                let free = available - sizes.iter().sum::<f32>();
+                if free <= 0.0 || growing.is_empty() {
+                    break;
+                }
+                let share = free / growing.len() as f32;
+                growing.retain(|index| {
+                    let GridTrack::MinMax(_, max) = self.track(axis, *index) else {
+                        return false;
+                    };
+                    sizes[*index] = f32::min(sizes[*index] + share, max);
+                    sizes[*index] < max
+                });
+            }
+
+            // Distribute the remaining space across the fraction tracks. Tracks whose content
+            // is larger than their share keep their content size and the others are resized.
+            let fraction = |index| match self.track(axis, index) {
+                GridTrack::Fraction(fraction) => fraction,
+                _ => 0.0,
+            };
+            let mut flexible: Vec<_> = (0..count).filter(|index| fraction(*index) > 0.0).collect();
+            for _ in 0..=count {
+                if flexible.is_empty() {
+                    break;
+                }
+                let fixed = (0..count)
+                    .filter(|index| !flexible.contains(index))
+                    .map(|index| sizes[index])
+                    .sum::<f32>();
+                let total_fraction = flexible.iter().map(|index| fraction(*index)).sum::<f32>();
+                let per_fraction = f32::max(available - fixed, 0.0) / total_fraction.max(1.0);
+
+                let flexible_count = flexible.len();
+                flexible.retain(|index| sizes[*index] <= per_fraction * fraction(*index));
+                if flexible.len() == flexible_count {
+                    for index in &flexible {
+                        sizes[*index] = per_fraction * fraction(*index);
+                    }
+                    break;
+                }
+            }
+        }
+
+        sizes.into_iter().map(GuiRounding::round_ui).collect()
+    }
+
+    /// Show the grid ui.
+    #[track_caller]
+    pub fn show<R>(
+        self,
+        ui: &mut Ui,
+        f: impl FnOnce(&mut FlexGridInstance) -> R,
+    ) -> InnerResponse<R> {
+        self.show_inside(ui, None, None, f).2
+    }
+
+    /// Show this grid in a Flex. See also [FlexInstance::add_grid].
+    #[track_caller]
+    pub fn show_in<R>(
+        self,
+        flex: &mut FlexInstance,
+        item: FlexItem,
+        f: impl FnOnce(&mut FlexGridInstance) -> R,
+    ) -> InnerResponse<R> {
+        flex.add_grid(item, self, f)
+    }
+}
+
+/// The cells an item covers, indexed by axis (0 is the column, 1 the row).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct GridArea {
+    start: [usize; 2],
+    span: [usize; 2],
+}
+
+impl GridArea {
+    fn overlaps(&self, other: &GridArea) -> bool {
+        (0..2).all(|axis| {
+            self.start[axis] < other.start[axis] + other.span[axis]
+                && other.start[axis] < self.start[axis] + self.span[axis]
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct GridItemState {
+    item: ItemState,
+    area: GridArea,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct GridState {
+    items: Vec<GridItemState>,
+    max_item_size: Vec2,
+    // We use this to keep track if there is a id clash.
+    frame_time: f64,
+    passes: u64,
+}
+
+impl Default for GridState {
+    fn default() -> Self {
+        Self {
+            items: vec![],
+            max_item_size: Vec2::ZERO,
+            // See FlexState::default
+            frame_time: f64::MAX,
+            passes: 0,
+        }
+    }
+}
+
+/// The position and size of the tracks along one axis.
+#[derive(Debug, Clone)]
+struct Tracks {
+    offsets: Vec<f32>,
+    sizes: Vec<f32>,
+    gap: f32,
+    origin: f32,
+}
+
+impl Tracks {
+    /// The total length of all tracks including the gaps.
+    fn length(&self) -> f32 {
+        self.offsets
+            .last()
+            .zip(self.sizes.last())
+            .map_or(0.0, |(offset, size)| offset + size - self.origin)
+    }
+
+    fn offset(&self, index: usize) -> f32 {
+        self.offsets.get(index).copied().unwrap_or_else(|| {
+            // Tracks we didn't know about last frame have no size yet
+            let end = if self.offsets.is_empty() {
+                self.origin
+            } else {
+                self.origin + self.length() + self.gap
+            };
+            end + (index - self.offsets.len()) as f32 * self.gap
+        })
+    }
+
+    /// The start and end position of a span of tracks.
+    fn range(&self, start: usize, span: usize) -> (f32, f32) {
+        let last = start + span - 1;
+        let end = self.offset(last) + self.sizes.get(last).copied().unwrap_or(0.0);
+        (self.offset(start), end)
+    }
+}
+
+/// An instance of a grid container, used to add items to the container.
+pub struct FlexGridInstance<'a> {
+    grid: &'a FlexGrid,
+    previous_items: &'a [GridItemState],
+    state: GridState,
+    ui: &'a mut Ui,
+    tracks: [Tracks; 2],
+    // Where to look for the next free cell, [column, row]
+    cursor: [usize; 2],
+    max_item_size: Vec2,
+}
+
+impl FlexGridInstance<'_> {
+    /// Get the ui of the grid container (e.g. to read the style or access the context).
+    pub fn ui(&self) -> &Ui {
+        self.ui
+    }
+
+    /// Access the underlying [`egui::Painter`].
+    pub fn painter(&self) -> &egui::Painter {
+        self.ui.painter()
+    }
+
+    /// Access the underlying [`egui::style::Visuals`].
+    pub fn visuals(&self) -> &egui::style::Visuals {
+        self.ui.visuals()
+    }
+
+    /// Access the underlying [`egui::style::Style`].
+    pub fn style(&self) -> &egui::style::Style {
+        self.ui.style()
+    }
+
+    /// Access the underlying [`egui::Spacing`].
+    pub fn spacing(&self) -> &egui::Spacing {
+        self.ui.spacing()
+    }
+
+    /// The number of columns of the grid.
+    pub fn column_count(&self) -> usize {
+        self.grid.column_count()
+    }
+
+    fn is_free(&self, area: &GridArea) -> bool {
+        !self.state.items.iter().any(|item| item.area.overlaps(area))
+    }
+
+    /// Find the cells for the next item. Items without an explicit row are placed in the next
+    /// free cell after the previous auto placed item.
+    fn place(&mut self, item: &FlexItemInner) -> GridArea {
+        let columns = self.grid.column_count();
+        let span = [
+            item.grid_span[0].unwrap_or(1).clamp(1, columns),
+            item.grid_span[1].unwrap_or(1).max(1),
+        ];
+        let column = item.grid_start[0].map(|column| column.min(columns - span[0]));
+
+        if let (Some(column), Some(row)) = (column, item.grid_start[1]) {
+            return GridArea {
+                start: [column, row],
+                span,
+            };
+        }
+
+        let mut start = item.grid_start[1].map_or(self.cursor, |row| [0, row]);
+        loop {
+            if let Some(column) = column {
+                if start[0] > column {
+                    start = [column, start[1] + 1];
+                } else {
+                    start[0] = column;
+                }
+            }
+            if start[0] + span[0] > columns {
+                start = [0, start[1] + 1];
+            }
+            if let Some(row) = item.grid_start[1]
+                && start[1] != row
+            {
+                // Nothing fits in the requested row, so we overlap the first cell
+                start = [column.unwrap_or(0), row];
+                break;
+            }
+            if self.is_free(&GridArea { start, span }) {
+                break;
+            }
+            start[0] += 1;
+        }
+
+        if item.grid_start[1].is_none() {
+            self.cursor = [start[0] + span[0], start[1]];
+        }
+
+        GridArea { start, span }
+    }
+
+    fn add_container<R>(&mut self, mut item: FlexItem, content: ContentFn<R>) -> InnerResponse<R> {
+        item.inner = item.inner.or(self.grid.default_item);
+
+        let area = self.place(&item.inner);
+        let (x_min, x_max) = self.tracks[0].range(area.start[0], area.span[0]);
+        let (y_min, y_max) = self.tracks[1].range(area.start[1], area.span[1]);
+        let cell_rect = Rect::from_x_y_ranges(x_min..=x_max, y_min..=y_max);
+
+        let previous = self.previous_items.get(self.state.items.len());
+        let align = match item.inner.align_self.unwrap_or_default() {
+            FlexAlign::Start => Some(Align2::LEFT_TOP),
+            FlexAlign::End => Some(Align2::RIGHT_BOTTOM),
+            FlexAlign::Center => Some(Align2::CENTER_CENTER),
+            FlexAlign::Stretch => None,
+        };
+
+        let res = add_positioned(
+            self.ui,
+            item,
+            cell_rect,
+            align,
+            previous.map(|previous| &previous.item),
+            self.max_item_size,
+            content,
+        );
+        let (inner, item) = res.inner;
+
+        self.state.items.push(GridItemState { item, area });
+
+        InnerResponse::new(inner, res.response)
+    }
+
+    /// Add a child ui to the grid.
+    /// It will be positioned based on [FlexItem::align_self_content].
+    pub fn add_ui<R>(
+        &mut self,
+        item: FlexItem,
+        content: impl FnOnce(&mut Ui) -> R,
+    ) -> InnerResponse<R> {
+        self.add_container(
+            item,
+            Box::new(|ui, container| container.content(ui, content)),
+        )
+    }
+
+    /// Add a [`Widget`] to the grid.
+    ///
+    /// Note: Unlike [`FlexInstance::add`], this takes a [`Widget`], since
+    /// [`crate::FlexWidget`] is implemented for [`FlexInstance`].
+    pub fn add<W: Widget>(&mut self, item: FlexItem, widget: W) -> Response {
+        self.add_widget(item, widget).inner
+    }
+
+    /// Add a [`Widget`] to the grid. See [`FlexInstance::add_widget`].
+    pub fn add_widget<W: Widget>(&mut self, item: FlexItem, widget: W) -> InnerResponse<Response> {
+        self.add_container(
+            item,
+            Box::new(|ui, container| container.content_widget(ui, widget)),
+        )
+    }
+
+    /// Add a nested flex container.
+    #[track_caller]
+    pub fn add_flex<R>(
+        &mut self,
+        item: FlexItem,
+        flex: Flex,
+        content: impl FnOnce(&mut FlexInstance) -> R,
+    ) -> InnerResponse<R> {
+        self.add_container(
+            item,
+            Box::new(|ui, container| container.content_flex(ui, flex, content)),
+        )
+    }
+
+    /// Add a nested grid container.
+    #[track_caller]
+    pub fn add_grid<R>(
+        &mut self,
+        item: FlexItem,
+        grid: FlexGrid,
+        content: impl FnOnce(&mut FlexGridInstance) -> R,
+    ) -> InnerResponse<R> {
+        self.add_container(
+            item,
+            Box::new(|ui, container| container.content_grid(ui, grid, content)),
+        )
+    }
+}
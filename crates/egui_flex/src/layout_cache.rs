@@ -0,0 +1,84 @@
+use crate::{Flex, RowData};
+use egui::{Context, Id, Vec2};
+use std::sync::Arc;
+
+/// The rows of the previous frame, positioned relative to the container origin, together with
+/// the inputs of [`Flex::layout_rows`] besides the item states. The item states are compared
+/// at the end of every pass anyway, so the cache is dropped there if they changed.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct LayoutCache {
+    flex: Flex,
+    available_size: Vec2,
+    size: [Option<f32>; 2],
+    gap: Vec2,
+    pub rows: Arc<[RowData]>,
+}
+
+impl LayoutCache {
+    pub fn new(
+        flex: &Flex,
+        available_size: Vec2,
+        size: [Option<f32>; 2],
+        gap: Vec2,
+        rows: Arc<[RowData]>,
+    ) -> Self {
+        Self {
+            flex: flex.clone(),
+            available_size,
+            size,
+            gap,
+            rows,
+        }
+    }
+
+    /// Were the rows laid out with these inputs?
+    pub fn matches(
+        &self,
+        flex: &Flex,
+        available_size: Vec2,
+        size: [Option<f32>; 2],
+        gap: Vec2,
+    ) -> bool {
+        self.available_size == available_size
+            && self.size == size
+            && self.gap == gap
+            && self.flex == *flex
+    }
+}
+
+/// Counts how often [`Flex`] containers could reuse their layout from the previous frame.
+///
+/// The layout is only recomputed when the available size, the item configs or the measured item
+/// sizes change. Useful to debug containers that relayout every frame.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LayoutCacheStats {
+    /// How often the cached layout was reused.
+    pub hits: u64,
+    /// How often the layout had to be recomputed.
+    pub misses: u64,
+}
+
+impl LayoutCacheStats {
+    fn id() -> Id {
+        Id::new("egui_flex_layout_cache_stats")
+    }
+
+    /// Get the counters of all flex containers since the last [`Self::reset`].
+    pub fn get(ctx: &Context) -> Self {
+        ctx.data(|data| data.get_temp(Self::id()).unwrap_or_default())
+    }
+
+    /// Reset the counters, e.g. at the start of a frame to get the stats per frame.
+    pub fn reset(ctx: &Context) {
+        ctx.data_mut(|data| data.remove::<Self>(Self::id()));
+    }
+
+    pub(crate) fn record(data: &mut egui::util::IdTypeMap, hit: bool) {
+        let stats = data.get_temp_mut_or_default::<Self>(Self::id());
+        if hit {
+            stats.hits += 1;
+        } else {
+            stats.misses += 1;
+        }
+    }
+}
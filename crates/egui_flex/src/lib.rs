@@ -2,9 +2,19 @@
 #![forbid(unsafe_code)]
 #![warn(missing_docs)]
 
+mod animation;
+mod breakpoint;
 mod flex_widget;
+mod grid;
+mod layout_cache;
 
+pub use crate::animation::FlexAnimation;
+use crate::animation::{FlexAnimator, next_shape_idx};
+pub use crate::breakpoint::{Breakpoint, FlexItemOverrides, FlexOverrides};
 pub use crate::flex_widget::FlexWidget;
+pub use crate::grid::{FlexGrid, FlexGridInstance, GridTrack};
+use crate::layout_cache::LayoutCache;
+pub use crate::layout_cache::LayoutCacheStats;
 use egui::emath::{GuiRounding, TSTransform};
 use egui::{
     Align, Align2, Direction, Frame, Id, InnerResponse, Layout, Margin, Pos2, Rect, Response,
@@ -12,21 +22,39 @@ use egui::{
 };
 use std::fmt::Debug;
 use std::mem;
+use std::sync::Arc;
 
 /// The direction in which the flex container should lay out its children.
+///
+/// More directions may be added in the future, so matches on this enum need a wildcard arm.
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 #[allow(missing_docs)]
+#[non_exhaustive]
 pub enum FlexDirection {
     #[default]
     Horizontal,
     Vertical,
+    /// Right to left, like css `row-reverse`.
+    HorizontalReverse,
+    /// Bottom to top, like css `column-reverse`.
+    VerticalReverse,
+}
+
+impl FlexDirection {
+    /// Is this [`FlexDirection::HorizontalReverse`] or [`FlexDirection::VerticalReverse`]?
+    pub fn is_reverse(self) -> bool {
+        matches!(
+            self,
+            FlexDirection::HorizontalReverse | FlexDirection::VerticalReverse
+        )
+    }
 }
 
 impl From<FlexDirection> for usize {
     fn from(direction: FlexDirection) -> Self {
         match direction {
-            FlexDirection::Horizontal => 0,
-            FlexDirection::Vertical => 1,
+            FlexDirection::Horizontal | FlexDirection::HorizontalReverse => 0,
+            FlexDirection::Vertical | FlexDirection::VerticalReverse => 1,
         }
     }
 }
@@ -105,6 +133,8 @@ pub struct Flex {
     wrap: bool,
     width: Option<Size>,
     height: Option<Size>,
+    breakpoints: Vec<(Breakpoint, FlexOverrides)>,
+    animation: Option<FlexAnimation>,
 }
 
 type FrameBuilder<'a> = Box<dyn FnOnce(&Ui, &Response) -> (Frame, TSTransform) + 'a>;
@@ -114,6 +144,7 @@ type FrameBuilder<'a> = Box<dyn FnOnce(&Ui, &Response) -> (Frame, TSTransform) +
 pub struct FlexItem<'a> {
     frame_builder: Option<FrameBuilder<'a>>,
     inner: FlexItemInner,
+    breakpoints: Vec<(Breakpoint, FlexItemOverrides)>,
 }
 
 impl FlexItem<'_> {
@@ -121,6 +152,7 @@ impl FlexItem<'_> {
         let FlexItem {
             mut inner,
             frame_builder,
+            breakpoints: _,
         } = self;
         if let Some(builder) = frame_builder {
             let (frame, transform) = builder(ui, response);
@@ -144,6 +176,11 @@ struct FlexItemInner {
     content_id: Option<Id>,
     sense: Option<Sense>,
     min_size: [Option<f32>; 2],
+    order: Option<i32>,
+    absolute: Option<(Align2, Vec2)>,
+    grid_start: [Option<usize>; 2],
+    grid_span: [Option<usize>; 2],
+    hidden: bool,
 }
 
 /// Only the things that are relevant on the next frame
@@ -154,6 +191,7 @@ struct FlexItemState {
     shrink: bool,
     margin: Margin,
     content_id: Option<Id>,
+    order: i32,
 }
 
 impl FlexItemInner {
@@ -173,6 +211,17 @@ impl FlexItemInner {
                 self.min_size[0].or(b.min_size[0]),
                 self.min_size[1].or(b.min_size[1]),
             ],
+            order: self.order.or(b.order),
+            absolute: self.absolute.or(b.absolute),
+            grid_start: [
+                self.grid_start[0].or(b.grid_start[0]),
+                self.grid_start[1].or(b.grid_start[1]),
+            ],
+            grid_span: [
+                self.grid_span[0].or(b.grid_span[0]),
+                self.grid_span[1].or(b.grid_span[1]),
+            ],
+            hidden: self.hidden || b.hidden,
         }
     }
 
@@ -183,6 +232,7 @@ impl FlexItemInner {
             shrink: self.shrink,
             margin: self.frame.map_or(Margin::ZERO, |f| f.total_margin().into()),
             content_id: self.content_id,
+            order: self.order.unwrap_or(0),
         }
     }
 }
@@ -309,6 +359,80 @@ impl<'a> FlexItem<'a> {
         self.inner.min_size[1] = min_height.into();
         self
     }
+
+    /// Change the position of the item in the flex container. Items are laid out in ascending
+    /// order, items with the same order in the order they were added.
+    ///
+    /// Default is 0.
+    pub fn order(mut self, order: i32) -> Self {
+        self.inner.order = Some(order);
+        self
+    }
+
+    /// Take the item out of the flex layout and place it relative to the flex container instead,
+    /// e.g. for badges or overlay buttons. The item is aligned within the container's rect by
+    /// `align` and then moved by `offset`.
+    ///
+    /// Absolute items don't take up space and are painted in the order they are added, so add
+    /// them last to show them on top of the other items.
+    /// Ignored in a [`FlexGrid`].
+    pub fn absolute(mut self, align: Align2, offset: impl Into<Vec2>) -> Self {
+        self.inner.absolute = Some((align, offset.into()));
+        self
+    }
+
+    /// Place the item in this (zero based) column of a [`FlexGrid`].
+    ///
+    /// If this is not set, the item will be placed in the next free cell.
+    /// Ignored in a [`Flex`].
+    pub fn grid_column(mut self, column: usize) -> Self {
+        self.inner.grid_start[0] = Some(column);
+        self
+    }
+
+    /// Place the item in this (zero based) row of a [`FlexGrid`].
+    ///
+    /// If this is not set, the item will be placed in the next free cell.
+    /// Ignored in a [`Flex`].
+    pub fn grid_row(mut self, row: usize) -> Self {
+        self.inner.grid_start[1] = Some(row);
+        self
+    }
+
+    /// How many columns of a [`FlexGrid`] should this item span?
+    ///
+    /// Default is 1. Ignored in a [`Flex`].
+    pub fn column_span(mut self, span: usize) -> Self {
+        self.inner.grid_span[0] = Some(span);
+        self
+    }
+
+    /// How many rows of a [`FlexGrid`] should this item span?
+    ///
+    /// Default is 1. Ignored in a [`Flex`].
+    pub fn row_span(mut self, span: usize) -> Self {
+        self.inner.grid_span[1] = Some(span);
+        self
+    }
+
+    /// Hide the item. Hidden items don't take up space, but their content is still shown in an
+    /// invisible ui, so their state (and the ids of the following items) stay the same when they
+    /// are shown again.
+    ///
+    /// Ignored in a [`FlexGrid`].
+    pub fn hidden(mut self, hidden: bool) -> Self {
+        self.inner.hidden = hidden;
+        self
+    }
+
+    /// Override the grow, basis or visibility of the item if the [`Breakpoint`] matches the
+    /// available size of the flex container. See [`Breakpoint`] for how overrides are applied.
+    ///
+    /// Ignored in a [`FlexGrid`].
+    pub fn at(mut self, breakpoint: impl Into<Breakpoint>, overrides: FlexItemOverrides) -> Self {
+        self.breakpoints.push((breakpoint.into(), overrides));
+        self
+    }
 }
 
 impl Flex {
@@ -327,6 +451,16 @@ impl Flex {
         Self::default().direction(FlexDirection::Vertical)
     }
 
+    /// Create a new horizontal flex container, laying out its items from right to left.
+    pub fn horizontal_reverse() -> Self {
+        Self::default().direction(FlexDirection::HorizontalReverse)
+    }
+
+    /// Create a new vertical flex container, laying out its items from bottom to top.
+    pub fn vertical_reverse() -> Self {
+        Self::default().direction(FlexDirection::VerticalReverse)
+    }
+
     /// Set the direction of the flex container.
     pub fn direction(mut self, direction: FlexDirection) -> Self {
         self.direction = direction;
@@ -471,23 +605,51 @@ impl Flex {
         self
     }
 
+    /// Override the direction, wrap or gap if the [`Breakpoint`] matches the available size of
+    /// the flex container. See [`Breakpoint`] for how overrides are applied.
+    ///
+    /// The state of the container and its items is kept when a breakpoint flips, so the new
+    /// layout is based on the sizes measured in the previous frame.
+    pub fn at(mut self, breakpoint: impl Into<Breakpoint>, overrides: FlexOverrides) -> Self {
+        self.breakpoints.push((breakpoint.into(), overrides));
+        self
+    }
+
+    /// Animate the items to their new position and size when the layout changes.
+    /// See [`FlexAnimation`] for details.
+    ///
+    /// Default: no animation
+    pub fn animate(mut self, animation: impl Into<Option<FlexAnimation>>) -> Self {
+        self.animation = animation.into();
+        self
+    }
+
     #[track_caller]
     #[allow(clippy::too_many_lines)]
     fn show_inside<R>(
-        mut self,
+        self,
         ui: &mut Ui,
         target_size: Option<Vec2>,
         max_item_size: Option<Vec2>,
         f: impl FnOnce(&mut FlexInstance) -> R,
-    ) -> (Vec2, FlexState, InnerResponse<R>) {
-        let id = if let Some(id_salt) = self.id_salt {
+    ) -> (Vec2, Option<f32>, InnerResponse<R>) {
+        let parent_rect = ui.max_rect();
+        // We ceil in order to prevent rounding errors to wrap the layout unexpectedly
+        // let available_size = target_size.unwrap_or(ui.available_size()).ceil();
+        let available_size = target_size.unwrap_or(parent_rect.size());
+
+        // The id doesn't depend on the breakpoints, so the state is kept when they flip
+        let flex = self.resolve_breakpoints(available_size);
+
+        let id = if let Some(id_salt) = flex.id_salt {
             ui.id().with(id_salt)
         } else {
             ui.auto_id_with("flex")
         };
-        let previous_state: FlexState = ui
+        // Taken instead of cloned, it's stored again at the end of the pass
+        let mut previous_state: FlexState = ui
             .ctx()
-            .memory(|mem| mem.data.get_temp(id).clone().unwrap_or_default());
+            .memory_mut(|mem| mem.data.remove_temp(id).unwrap_or_default());
 
         let frame_time = ui.ctx().input(|i| i.time);
         let passes = ui.ctx().cumulative_pass_nr();
@@ -497,14 +659,14 @@ impl Flex {
             panic!("Id clash in flex container! Id: {id:?}");
         }
 
-        let width = self.width.or_else(|| {
+        let width = flex.width.or_else(|| {
             if ui.layout().horizontal_justify() {
                 Some(Size::Percent(1.0))
             } else {
                 None
             }
         });
-        let height = self.height.or_else(|| {
+        let height = flex.height.or_else(|| {
             if ui.layout().vertical_justify() {
                 Some(Size::Percent(1.0))
             } else {
@@ -512,27 +674,26 @@ impl Flex {
             }
         });
 
-        let layout = match self.direction {
-            FlexDirection::Horizontal => Layout::left_to_right(Align::Min),
-            FlexDirection::Vertical => Layout::top_down(Align::Min),
+        // Only the rows are reversed, see FlexInstance::row_ui
+        let layout = match flex.direction {
+            FlexDirection::Horizontal | FlexDirection::HorizontalReverse => {
+                Layout::left_to_right(Align::Min)
+            }
+            FlexDirection::Vertical | FlexDirection::VerticalReverse => {
+                Layout::top_down(Align::Min)
+            }
         };
 
         let mut state_changed = false;
 
-        let parent_rect = ui.max_rect();
-
         let r = ui.scope_builder(
             UiBuilder::new()
                 .layout(layout)
                 .max_rect(ui.available_rect_before_wrap().round_ui()),
             |ui| {
-                let gap = self.gap.unwrap_or(ui.spacing_mut().item_spacing);
+                let gap = flex.gap.unwrap_or(ui.spacing_mut().item_spacing);
                 let original_item_spacing = mem::replace(&mut ui.spacing_mut().item_spacing, gap);
 
-                // We ceil in order to prevent rounding errors to wrap the layout unexpectedly
-                // let available_size = target_size.unwrap_or(ui.available_size()).ceil();
-                let available_size = target_size.unwrap_or(parent_rect.size());
-
                 // TODO: Is this right? I would expect Vec2::min...
                 let size_origin = Vec2::max(
                     target_size.unwrap_or(parent_rect.size()),
@@ -546,35 +707,70 @@ impl Flex {
                 ];
 
                 let direction = usize::from(!ui.layout().main_dir().is_horizontal());
+
+                // In reversed layouts the min rect starts at the end, so we use the max rect
+                let origin = ui.max_rect().min;
                 let cross_direction = 1 - direction;
 
-                // TODO: I think it should be possible to cache the layout
-                let rows = self.layout_rows(
-                    &previous_state,
-                    available_size,
-                    size,
-                    gap,
-                    direction,
-                    ui.min_rect().min,
-                );
+                // The rows only need to be recomputed if their inputs changed. The cache is
+                // dropped at the end of a pass if the item states changed, so only the config
+                // and sizes need to be checked here.
+                let (cache_hit, layout_cache) = match previous_state.layout_cache.take() {
+                    Some(cache) if cache.matches(&flex, available_size, size, gap) => (true, cache),
+                    _ => {
+                        let rows =
+                            flex.layout_rows(&previous_state, available_size, size, gap, direction);
+                        (
+                            false,
+                            LayoutCache::new(&flex, available_size, size, gap, rows.into()),
+                        )
+                    }
+                };
+                let rows = Arc::clone(&layout_cache.rows);
 
                 // Lets be careful and round this completely
                 let max_item_size = max_item_size.unwrap_or(available_size).round();
 
+                // Where each item goes, by the index it is added at
+                let mut slots = vec![(rows.len(), 0); previous_state.items.len()];
+                for (row_idx, row) in rows.iter().enumerate() {
+                    for (row_index, idx) in row.indices.iter().enumerate() {
+                        slots[*idx] = (row_idx, row_index);
+                    }
+                }
+
+                let animator = flex
+                    .animation
+                    .map(|animation| FlexAnimator::load(ui, id, animation, origin));
+
                 let mut instance = FlexInstance {
                     current_row: 0,
                     current_row_index: 0,
-                    flex: &self,
+                    flex: &flex,
                     state: FlexState {
                         items: vec![],
+                        absolute_items: vec![],
+                        container_rect: Rect::ZERO,
                         max_item_size,
                         frame_time,
                         passes,
                         shrunk_item_cross_size: None,
+                        layout_cache: Some(layout_cache),
                     },
+                    slots,
+                    origin,
+                    previous_container_rect: previous_state.container_rect,
+                    previous_absolute_items: &previous_state.absolute_items,
+                    available_size,
+                    animator,
                     direction,
-                    row_ui: FlexInstance::row_ui(ui, rows.first()),
+                    row_ui: FlexInstance::row_ui(
+                        ui,
+                        rows.first().and_then(|row| row.rect_at(origin)),
+                        flex.direction,
+                    ),
                     ui,
+                    final_rects: vec![None; rows.len()],
                     rows,
                     max_item_size,
                     item_spacing: original_item_spacing,
@@ -600,6 +796,15 @@ impl Flex {
 
                 min_size = min_size.min(max_item_size);
 
+                let container_rect = instance
+                    .final_rects
+                    .iter()
+                    .flatten()
+                    .copied()
+                    .reduce(Rect::union)
+                    .unwrap_or(Rect::from_min_size(origin, Vec2::ZERO));
+                instance.state.container_rect = container_rect.translate(-origin.to_vec2());
+
                 // TODO: We should be able to calculate the min_size by looking at the rows at the
                 // max item size, but form some reason this doesn't work correctly
                 // This would fix wrapping in nested flexes
@@ -624,19 +829,47 @@ impl Flex {
                     if previous_state.shrunk_item_cross_size.is_some() {
                         instance.state.shrunk_item_cross_size = None;
                     }
+                    // The rows were laid out with the previous item states
+                    instance.state.layout_cache = None;
+                }
+                // Absolute items are placed based on the container rect of the previous frame
+                if previous_state.absolute_items != instance.state.absolute_items
+                    || (!instance.state.absolute_items.is_empty()
+                        && previous_state.container_rect != instance.state.container_rect)
+                {
+                    state_changed = true;
                 }
 
-                instance.ui.ctx().memory_mut(|mem| {
-                    mem.data.insert_temp(id, instance.state.clone());
+                // A pass that will be discarded was laid out with outdated sizes, so it shouldn't
+                // change where the items are animated to
+                let mut animator = instance
+                    .animator
+                    .take()
+                    .filter(|_| !state_changed && !instance.ui.ctx().will_discard());
+                if let Some(animator) = &mut animator {
+                    animator.paint_removed(instance.ui.painter());
+                }
+                let shrunk_item_cross_size = instance.state.shrunk_item_cross_size;
+                let animating = instance.ui.ctx().memory_mut(|mem| {
+                    mem.data.insert_temp(id, instance.state);
+                    LayoutCacheStats::record(&mut mem.data, cache_hit);
+                    animator.is_some_and(|animator| {
+                        animator.store(&mut mem.data, previous_state.frame_time != f64::MAX)
+                    })
                 });
+                if animating {
+                    instance.ui.ctx().request_repaint();
+                }
 
-                instance.rows.iter().for_each(|row| {
-                    if let Some(final_rect) = row.final_rect {
+                instance
+                    .final_rects
+                    .iter()
+                    .flatten()
+                    .for_each(|final_rect| {
                         // instance.ui.allocate_rect(final_rect, Sense::hover(), final_rect.size());
-                        instance.ui.allocate_rect(final_rect, Sense::hover());
-                    }
-                });
-                (min_size, instance.state, r)
+                        instance.ui.allocate_rect(*final_rect, Sense::hover());
+                    });
+                (min_size, shrunk_item_cross_size, r)
             },
         );
 
@@ -654,15 +887,16 @@ impl Flex {
         )
     }
 
+    /// Calculate the rows based on the items of the previous frame. The rows are positioned
+    /// relative to the container origin, so they can be cached.
     #[allow(clippy::too_many_lines)]
     fn layout_rows(
-        &mut self,
+        &self,
         state: &FlexState,
         available_size: Vec2,
         size: [Option<f32>; 2],
         gap: Vec2,
         direction: usize,
-        min_position: Pos2,
     ) -> Vec<RowData> {
         let cross_direction = 1 - direction;
 
@@ -675,7 +909,12 @@ impl Flex {
 
         let mut shrink_index = None;
 
-        for (idx, item) in state.items.iter().enumerate() {
+        // Items are laid out by their order, the sort is stable so equal orders keep the
+        // insertion order
+        let mut items: Vec<_> = state.items.iter().enumerate().collect();
+        items.sort_by_key(|(_, item)| item.config.order);
+
+        for (idx, item) in items {
             if item.config.shrink && !self.wrap {
                 debug_assert!(
                     shrink_index.is_none(),
@@ -704,6 +943,7 @@ impl Flex {
             }
             current_row.total_grow += item.config.grow.unwrap_or(0.0);
             current_row.items.push(item.clone());
+            current_row.indices.push(idx);
             if item.min_size_with_margin()[cross_direction] > current_row.cross_size {
                 current_row.cross_size = item.min_size_with_margin()[cross_direction];
             }
@@ -730,10 +970,12 @@ impl Flex {
         let mut _extra_cross_gap_end = 0.0; // TODO: How to handle extra end space?
         let mut extra_cross_space_per_row = 0.0;
 
-        if !self.wrap {
-            self.align_content = FlexAlignContent::Stretch;
-        }
-        match self.align_content {
+        let align_content = if self.wrap {
+            self.align_content
+        } else {
+            FlexAlignContent::Stretch
+        };
+        match align_content {
             FlexAlignContent::Start => {
                 _extra_cross_gap_end = extra_cross_space;
             }
@@ -757,7 +999,7 @@ impl Flex {
             }
         }
 
-        let mut row_position = min_position;
+        let mut row_position = Pos2::ZERO;
 
         row_position[cross_direction] += extra_cross_gap_start;
 
@@ -765,6 +1007,11 @@ impl Flex {
         for (_idx, row) in &mut rows.iter_mut().enumerate() {
             let mut row_size = Vec2::ZERO;
             row_size[direction] = available_length;
+            // Reversed rows start at the end, so without an explicit size they should only be as
+            // long as their content
+            if self.direction.is_reverse() && size[direction].is_none() && row_count == 1 {
+                row_size[direction] = f32::min(row.total_size, available_length);
+            }
             row_size[cross_direction] = row.cross_size + extra_cross_space_per_row;
             // TODO: Should there be an option to also limit in the cross dir?
             // if size[cross_direction].is_some() {
@@ -837,9 +1084,11 @@ impl Flex {
     }
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 struct RowData {
     items: Vec<ItemState>,
+    // The index each item was added at
+    indices: Vec<usize>,
     total_size: f32,
     total_grow: f32,
     extra_space: f32,
@@ -847,8 +1096,41 @@ struct RowData {
     extra_start_gap: f32,
     cross_size: f32,
     cross_size_with_extra_space: f32,
+    // Relative to the container origin
     rect: Option<Rect>,
-    final_rect: Option<Rect>,
+}
+
+impl RowData {
+    /// The rect of the row in a container at `origin`.
+    fn rect_at(&self, origin: Pos2) -> Option<Rect> {
+        self.rect.map(|rect| rect.translate(origin.to_vec2()))
+    }
+
+    /// How much an item grows in the main direction.
+    fn extra_length(&self, item: &ItemState) -> f32 {
+        let grow = item.config.grow.unwrap_or(0.0);
+        if grow > 0.0 && self.total_grow > 0.0 {
+            f32::max(self.extra_space * grow / self.total_grow, 0.0)
+        } else {
+            0.0
+        }
+    }
+
+    /// The offset of an item from the start of the row, based on the previous frame's sizes.
+    fn item_offset(&self, index: usize, direction: usize, gap: f32) -> f32 {
+        self.extra_start_gap
+            + self.items[..index]
+                .iter()
+                .map(|item| {
+                    let mut length =
+                        item.min_size_with_margin()[direction] + self.extra_length(item);
+                    if item.config.shrink && self.extra_space < 0.0 {
+                        length = f32::max(length + self.extra_space, 0.0);
+                    }
+                    length + gap + self.extra_gap
+                })
+                .sum::<f32>()
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -868,24 +1150,31 @@ impl ItemState {
 #[derive(Debug, Clone, PartialEq)]
 struct FlexState {
     items: Vec<ItemState>,
+    absolute_items: Vec<ItemState>,
+    // Relative to the top left of the available rect
+    container_rect: Rect,
     max_item_size: Vec2,
     // We use this to keep track if there is a id clash.
     frame_time: f64,
     passes: u64,
     // If an item shrunk it might grow in cross size. We need to expand other items to match this.
     shrunk_item_cross_size: Option<f32>,
+    layout_cache: Option<LayoutCache>,
 }
 
 impl Default for FlexState {
     fn default() -> Self {
         Self {
             items: vec![],
+            absolute_items: vec![],
+            container_rect: Rect::ZERO,
             max_item_size: Vec2::ZERO,
             // frame_time is 0 on first frame for some eugi integrations which triggers the
             // frame_time debug assert. We set it to f64::MAX to prevent this.
             frame_time: f64::MAX,
             passes: 0,
             shrunk_item_cross_size: None,
+            layout_cache: None,
         }
     }
 }
@@ -897,20 +1186,50 @@ pub struct FlexInstance<'a> {
     current_row_index: usize,
     state: FlexState,
     ui: &'a mut Ui,
-    rows: Vec<RowData>,
+    // Shared with the layout cache, so the rects are relative to the origin
+    rows: Arc<[RowData]>,
+    // The rect the items of each row were actually shown in
+    final_rects: Vec<Option<Rect>>,
     direction: usize,
     row_ui: Ui,
     max_item_size: Vec2,
     // Original item spacing to store when showing children
     item_spacing: Vec2,
     size: [Option<f32>; 2],
+    // The row and row index of each item, by the index it is added at
+    slots: Vec<(usize, usize)>,
+    origin: Pos2,
+    previous_container_rect: Rect,
+    previous_absolute_items: &'a [ItemState],
+    // The size the breakpoints of the items are resolved against
+    available_size: Vec2,
+    animator: Option<FlexAnimator>,
 }
 
 impl FlexInstance<'_> {
-    fn row_ui(parent: &mut Ui, row: Option<&RowData>) -> Ui {
-        let rect = row.map_or(parent.max_rect(), |row| row.rect.unwrap());
+    fn row_ui(parent: &mut Ui, rect: Option<Rect>, direction: FlexDirection) -> Ui {
+        let rect = rect.unwrap_or(parent.max_rect());
+
+        let mut builder = UiBuilder::new().max_rect(rect);
+        match direction {
+            FlexDirection::HorizontalReverse => {
+                builder = builder.layout(Layout::right_to_left(Align::Min));
+            }
+            FlexDirection::VerticalReverse => {
+                builder = builder.layout(Layout::bottom_up(Align::Min));
+            }
+            FlexDirection::Horizontal | FlexDirection::Vertical => {}
+        }
+        parent.new_child(builder)
+    }
 
-        parent.new_child(UiBuilder::new().max_rect(rect))
+    /// Create the ui of the current row.
+    fn next_row_ui(&mut self) {
+        let rect = self
+            .rows
+            .get(self.current_row)
+            .and_then(|row| row.rect_at(self.origin));
+        self.row_ui = FlexInstance::row_ui(self.ui, rect, self.flex.direction);
     }
 
     /// Get the direction of the flex container.
@@ -918,14 +1237,14 @@ impl FlexInstance<'_> {
         self.flex.direction
     }
 
-    /// Is the flex container horizontal?
+    /// Is the flex container horizontal (or horizontal reverse)?
     pub fn is_horizontal(&self) -> bool {
-        self.flex.direction == FlexDirection::Horizontal
+        self.direction == 0
     }
 
-    /// Is the flex container vertical?
+    /// Is the flex container vertical (or vertical reverse)?
     pub fn is_vertical(&self) -> bool {
-        self.flex.direction == FlexDirection::Vertical
+        self.direction == 1
     }
 
     /// Get the ui of the flex container (e.g. to read the style or access the context).
@@ -968,22 +1287,137 @@ impl FlexInstance<'_> {
         self.ui.new_child(ui_builder)
     }
 
+    fn add_absolute<R>(
+        &mut self,
+        item: FlexItem,
+        (align, offset): (Align2, Vec2),
+        content: ContentFn<R>,
+    ) -> InnerResponse<R> {
+        let container_rect = self
+            .previous_container_rect
+            .translate(self.origin.to_vec2());
+        let previous = self
+            .previous_absolute_items
+            .get(self.state.absolute_items.len());
+
+        // Absolute items shouldn't take up space in the flex container
+        let mut overlay_ui = self.ui.new_child(UiBuilder::new().max_rect(container_rect));
+        let res = add_positioned(
+            &mut overlay_ui,
+            item,
+            container_rect.translate(offset),
+            Some(align),
+            previous,
+            self.max_item_size,
+            content,
+        );
+        let (inner, item) = res.inner;
+        self.state.absolute_items.push(item);
+
+        InnerResponse::new(inner, res.response)
+    }
+
+    fn add_hidden<R>(&mut self, item: FlexItemInner, content: ContentFn<R>) -> InnerResponse<R> {
+        // Hidden items aren't part of the layout. The content is still shown, nested like in
+        // add_container but without allocating space, so it keeps its ids and state.
+        let rect = self.row_ui.available_rect_before_wrap();
+        let mut hidden_ui = self.row_ui.new_child(UiBuilder::new().max_rect(rect));
+        let exit = self.animator.as_mut().and_then(|animator| {
+            let key = animator.key(item.content_id);
+            animator.exit(key)
+        });
+
+        let frame = item.frame.unwrap_or_default();
+        let mut transform = item.transform.unwrap_or_default();
+        let margin = frame.inner_margin + frame.outer_margin;
+        let frame_rect = exit.as_ref().map_or(rect, |exit| exit.rect);
+
+        let mut child_ui = hidden_ui.new_child(
+            UiBuilder::new()
+                .max_rect(frame_rect)
+                .layout(*self.ui.layout()),
+        );
+        child_ui.spacing_mut().item_spacing = self.item_spacing;
+        if let Some(exit) = &exit {
+            // Fade out at the last position
+            child_ui.disable();
+            child_ui.multiply_opacity(exit.opacity);
+            transform = exit.transform(frame_rect) * transform;
+        } else {
+            child_ui.set_invisible();
+        }
+
+        let res = child_ui
+            .with_visual_transform(transform, |ui| {
+                frame
+                    .show(ui, |ui| {
+                        content(
+                            ui,
+                            FlexContainerUi {
+                                direction: self.direction,
+                                content_rect: frame_rect - margin,
+                                frame_rect,
+                                margin,
+                                max_item_size: self.max_item_size - margin.sum(),
+                                target_inner_size: (frame_rect - margin).size(),
+                                item,
+                                shrunk: false,
+                            },
+                        )
+                    })
+                    .inner
+            })
+            .inner;
+
+        InnerResponse::new(res.inner, hidden_ui.response())
+    }
+
     #[allow(clippy::too_many_lines)] // TODO: Refactor this to be more readable
     fn add_container<R>(&mut self, mut item: FlexItem, content: ContentFn<R>) -> InnerResponse<R> {
-        let row = self.rows.get_mut(self.current_row);
+        item.resolve_breakpoints(self.available_size);
+        item.inner = item.inner.or(self.flex.default_item);
+
+        if item.inner.hidden {
+            return self.add_hidden(item.inner, content);
+        }
 
-        if let Some(row) = &row {
+        if let Some(absolute) = item.inner.absolute {
+            return self.add_absolute(item, absolute, content);
+        }
+
+        let animation_key = self
+            .animator
+            .as_mut()
+            .map(|animator| animator.key(item.inner.content_id));
+
+        // If the items are reordered, this item might not be the next one in the current row
+        let slot = self
+            .slots
+            .get(self.state.items.len())
+            .copied()
+            .unwrap_or((self.rows.len(), 0));
+        let moved = slot != (self.current_row, self.current_row_index);
+        if moved {
+            (self.current_row, self.current_row_index) = slot;
+            self.next_row_ui();
+            if let Some(row) = self.rows.get(self.current_row) {
+                let gap = self.row_ui.spacing().item_spacing[self.direction];
+                self.row_ui
+                    .add_space(row.item_offset(self.current_row_index, self.direction, gap));
+            }
+        }
+
+        let row = self.rows.get(self.current_row);
+
+        if let Some(row) = row
+            && !moved
+        {
             if self.current_row_index == 0 {
                 self.row_ui.add_space(row.extra_start_gap);
             } else {
                 self.row_ui.add_space(row.extra_gap);
             }
-            row.extra_start_gap
-        } else {
-            0.0
-        };
-
-        item.inner = item.inner.or(self.flex.default_item);
+        }
 
         let res = self.row_ui.scope_builder(
             UiBuilder {
@@ -997,24 +1431,15 @@ impl FlexInstance<'_> {
                 let basis = item.basis;
 
                 let frame = item.frame.unwrap_or_default();
-                let transform = item.transform.unwrap_or_default();
+                let mut transform = item.transform.unwrap_or_default();
                 let margin = frame.inner_margin + frame.outer_margin;
 
                 let res = if let Some(row) = row {
                     let row_item_count = row.items.len();
                     // TODO: Handle when this is not set (Why doesn't this fail?)
-                    let item_state = row.items.get_mut(self.current_row_index).unwrap();
-
-                    let extra_length =
-                        if item_state.config.grow.unwrap_or(0.0) > 0.0 && row.total_grow > 0.0 {
-                            f32::max(
-                                row.extra_space * item_state.config.grow.unwrap_or(0.0)
-                                    / row.total_grow,
-                                0.0,
-                            )
-                        } else {
-                            0.0
-                        };
+                    let item_state = row.items.get(self.current_row_index).unwrap();
+
+                    let extra_length = row.extra_length(item_state);
 
                     let do_shrink = item_state.config.shrink && row.extra_space < 0.0;
 
@@ -1052,18 +1477,33 @@ impl FlexInstance<'_> {
                     };
 
                     let mut max_rect = ui.max_rect();
+                    let reverse = self.flex.direction.is_reverse();
 
                     // TODO: Is this right?
                     if total_size[self.direction] > max_rect.size()[self.direction] {
-                        let mut size = max_rect.size();
-                        size[self.direction] = total_size[self.direction];
-                        max_rect = Rect::from_min_size(max_rect.min, size);
+                        if reverse {
+                            max_rect.min[self.direction] =
+                                max_rect.max[self.direction] - total_size[self.direction];
+                        } else {
+                            max_rect.max[self.direction] =
+                                max_rect.min[self.direction] + total_size[self.direction];
+                        }
                     }
 
                     let frame_rect = match frame_align {
-                        None => Rect::from_min_size(parent_min_rect.min, total_size),
+                        None => {
+                            // In reversed layouts the cursor is at the end of the item
+                            let mut min = parent_min_rect.min;
+                            if reverse {
+                                min[self.direction] -= total_size[self.direction];
+                            }
+                            Rect::from_min_size(min, total_size)
+                        }
                         Some(align) => {
                             let mut align2 = Align2::LEFT_TOP;
+                            if reverse {
+                                align2[self.direction] = Align::Max;
+                            }
                             align2[1 - self.direction] = align;
                             align2.align_size_within_rect(total_size, max_rect)
                         }
@@ -1073,6 +1513,15 @@ impl FlexInstance<'_> {
                     //     .debug_painter()
                     //     .debug_rect(frame_rect, egui::Color32::RED, "");
 
+                    // The item is laid out in its new rect and only painted at the animated one,
+                    // so its size doesn't change while the animation runs
+                    let mut opacity = 1.0;
+                    if let (Some(animator), Some(key)) = (&mut self.animator, animation_key) {
+                        let animated = animator.item(key, frame_rect);
+                        transform = animated.transform(frame_rect) * transform;
+                        opacity = animated.opacity;
+                    }
+
                     let mut target_inner_size = item_state.inner_size;
 
                     if do_shrink {
@@ -1119,9 +1568,15 @@ impl FlexInstance<'_> {
                     //     .debug_painter()
                     //     .debug_rect(frame_rect, egui::Color32::GREEN, "");
 
-                    let mut child_ui =
-                        ui.new_child(UiBuilder::new().max_rect(frame_rect).layout(*ui.layout()));
+                    // The content is always laid out forwards, even if the row is reversed
+                    let mut child_ui = ui.new_child(
+                        UiBuilder::new()
+                            .max_rect(frame_rect)
+                            .layout(*self.ui.layout()),
+                    );
                     child_ui.spacing_mut().item_spacing = self.item_spacing;
+                    child_ui.multiply_opacity(opacity);
+                    let shapes_start = next_shape_idx(&child_ui);
 
                     let res = child_ui
                         .with_visual_transform(transform, |ui| {
@@ -1145,6 +1600,9 @@ impl FlexInstance<'_> {
                         })
                         .inner;
                     // let (_, _r) = ui.allocate_space(child_ui.min_rect().size(), child_ui.min_rect().size());
+                    if let (Some(animator), Some(key)) = (&mut self.animator, animation_key) {
+                        animator.capture(&child_ui, key, shapes_start);
+                    }
                     let (_, _r) = ui.allocate_space(child_ui.min_rect().size());
 
                     let mut inner_size = res.intrinsic_size;
@@ -1229,12 +1687,13 @@ impl FlexInstance<'_> {
         //     }
         //     self.row_ui.spacing_mut().item_spacing = spacing;
         // }
-        if let Some(row) = self.rows.get_mut(self.current_row) {
+        if let Some(row_final_rect) = self.final_rects.get_mut(self.current_row) {
             let mut final_rect = self.row_ui.min_rect();
             if self.size[self.direction].is_some() {
                 final_rect = final_rect.union(self.row_ui.max_rect());
             }
-            row.final_rect = Some(final_rect);
+            // Reordered items may be added to a row ui that was recreated for them
+            *row_final_rect = Some(row_final_rect.map_or(final_rect, |r| r.union(final_rect)));
         }
 
         self.state.items.push(item);
@@ -1243,7 +1702,7 @@ impl FlexInstance<'_> {
         if is_last_item {
             self.current_row += 1;
             self.current_row_index = 0;
-            self.row_ui = FlexInstance::row_ui(self.ui, self.rows.get(self.current_row));
+            self.next_row_ui();
         }
 
         InnerResponse::new(inner, res.response)
@@ -1295,7 +1754,7 @@ impl FlexInstance<'_> {
             .grow
             .or(self.flex.default_item.grow)
             .is_some_and(|g| g > 0.0)
-            && self.flex.direction != flex.direction
+            && self.direction != usize::from(flex.direction)
         {
             flex.align_content = FlexAlignContent::Stretch;
         }
@@ -1306,6 +1765,20 @@ impl FlexInstance<'_> {
         )
     }
 
+    /// Add a nested grid container.
+    #[track_caller]
+    pub fn add_grid<R>(
+        &mut self,
+        item: FlexItem,
+        grid: FlexGrid,
+        content: impl FnOnce(&mut FlexGridInstance) -> R,
+    ) -> InnerResponse<R> {
+        self.add_container(
+            item,
+            Box::new(|ui, container| container.content_grid(ui, grid, content)),
+        )
+    }
+
     /// Adds an empty item with flex-grow 1.0.
     pub fn grow(&mut self) -> Response {
         self.add_ui(FlexItem::new().grow(1.0), |_| {}).response
@@ -1314,6 +1787,96 @@ impl FlexInstance<'_> {
 
 type ContentFn<'a, R> = Box<dyn FnOnce(&mut Ui, FlexContainerUi) -> FlexContainerResponse<R> + 'a>;
 
+/// Show an item at a known position (a grid cell or relative to the container for absolute
+/// items). The item is aligned within `area` by `align` or stretched to fill it if `None`.
+fn add_positioned<R>(
+    ui: &mut Ui,
+    item: FlexItem,
+    area: Rect,
+    align: Option<Align2>,
+    previous: Option<&ItemState>,
+    max_item_size: Vec2,
+    content: ContentFn<R>,
+) -> InnerResponse<(R, ItemState)> {
+    ui.scope_builder(
+        UiBuilder {
+            disabled: item.inner.disabled,
+            sense: item.inner.sense.or(Some(Sense::hover())),
+            max_rect: Some(area),
+            ..Default::default()
+        },
+        |ui| {
+            let item = item.build_into_inner(ui, &ui.response());
+
+            let frame = item.frame.unwrap_or_default();
+            let transform = item.transform.unwrap_or_default();
+            let margin = frame.inner_margin + frame.outer_margin;
+
+            // We don't know the size of new items, so we hide them until the next pass
+            if previous.is_none() {
+                ui.set_invisible();
+            }
+            let target_inner_size = previous.map_or(Vec2::ZERO, |p| p.inner_size);
+            let min_size = previous.map_or(Vec2::ZERO, ItemState::min_size_with_margin);
+
+            let frame_rect =
+                align.map_or(area, |align| align.align_size_within_rect(min_size, area));
+
+            let content_align = item.align_content.unwrap_or(Align2::CENTER_CENTER);
+            let mut content_rect =
+                content_align.align_size_within_rect(target_inner_size, frame_rect - margin);
+
+            // Like in Flex, we allow the content to grow up to the available size
+            let max_content_size = max_item_size - margin.sum();
+            content_rect.set_width(max_content_size.x);
+            content_rect.set_height(max_content_size.y);
+
+            let mut child_ui =
+                ui.new_child(UiBuilder::new().max_rect(frame_rect).layout(*ui.layout()));
+
+            let res = child_ui
+                .with_visual_transform(transform, |ui| {
+                    frame
+                        .show(ui, |ui| {
+                            content(
+                                ui,
+                                FlexContainerUi {
+                                    direction: 0,
+                                    content_rect,
+                                    frame_rect,
+                                    margin,
+                                    max_item_size: max_content_size,
+                                    target_inner_size,
+                                    item,
+                                    shrunk: false,
+                                },
+                            )
+                        })
+                        .inner
+                })
+                .inner;
+            ui.advance_cursor_after_rect(child_ui.min_rect());
+
+            let inner_size = res.intrinsic_size;
+            let item = ItemState {
+                inner_size: inner_size.round_ui(),
+                id: ui.id(),
+                inner_min_size: Vec2::max(
+                    Vec2::new(
+                        item.min_size[0].unwrap_or_default(),
+                        item.min_size[1].unwrap_or_default(),
+                    ) - frame.total_margin().sum(),
+                    inner_size,
+                )
+                .round_ui(),
+                config: item.into_state(),
+            };
+
+            (res.inner, item)
+        },
+    )
+}
+
 /// Helper to show the inner content of a container.
 pub struct FlexContainerUi {
     direction: usize,
@@ -1430,12 +1993,11 @@ impl FlexContainerUi {
 
         target_size = Vec2::min(target_size, max_item_size);
 
-        let (min_size, state, res) =
+        let (min_size, shrunk_item_cross_size, res) =
             flex.show_inside(ui, Some(target_size), Some(max_item_size), content);
 
-        let shrunk_item_cross_size = state
-            .shrunk_item_cross_size
-            .filter(|_| self.shrunk && child_direction == self.direction);
+        let shrunk_item_cross_size =
+            shrunk_item_cross_size.filter(|_| self.shrunk && child_direction == self.direction);
 
         FlexContainerResponse {
             inner: res.inner,
@@ -1445,6 +2007,36 @@ impl FlexContainerUi {
         }
     }
 
+    /// Add a nested grid container.
+    #[track_caller]
+    pub fn content_grid<R>(
+        self,
+        ui: &mut Ui,
+        grid: FlexGrid,
+        content: impl FnOnce(&mut FlexGridInstance) -> R,
+    ) -> FlexContainerResponse<R> {
+        let Self {
+            max_item_size,
+            target_inner_size,
+            ..
+        } = self;
+
+        ui.set_width(ui.available_width());
+        ui.set_height(ui.available_height());
+
+        let target_size = Vec2::min(target_inner_size, max_item_size);
+
+        let (min_size, _state, res) =
+            grid.show_inside(ui, Some(target_size), Some(max_item_size), content);
+
+        FlexContainerResponse {
+            inner: res.inner,
+            intrinsic_size: min_size,
+            max_size: ui.available_size(),
+            shrunk_item_cross_size: None,
+        }
+    }
+
     /// Add a widget to the container.
     pub fn content_widget(
         self,
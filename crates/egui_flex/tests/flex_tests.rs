@@ -1,12 +1,16 @@
 use eframe::emath::Vec2;
 use egui::{
-    Align, Button, Checkbox, DragValue, Frame, Id, Label, Layout, ScrollArea, TextEdit, Ui,
+    Align, Align2, Button, Checkbox, Color32, DragValue, Frame, Id, Label, Layout, Rect,
+    ScrollArea, Sense, Shape, TextEdit, Ui,
+};
+use egui_flex::{
+    Breakpoint, Flex, FlexAlign, FlexAlignContent, FlexAnimation, FlexDirection, FlexItem,
+    FlexItemOverrides, FlexJustify, FlexOverrides, LayoutCacheStats, Size, item,
 };
-use egui_flex::{Flex, FlexAlign, FlexAlignContent, FlexItem, FlexJustify, Size, item};
 use egui_kittest::wgpu::WgpuTestRenderer;
 use egui_kittest::{Harness, TestRenderer};
 use rstest::rstest;
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
 
 fn snapshot_name() -> String {
     let thread = std::thread::current();
@@ -443,3 +447,297 @@ fn truncate_shrink() {
         result.unwrap();
     }
 }
+
+#[test]
+fn order_and_reverse() {
+    let rects = RefCell::new(vec![]);
+
+    let mut harness = Harness::new_ui(|ui| {
+        let mut rects = rects.borrow_mut();
+        rects.clear();
+
+        Flex::horizontal().show(ui, |flex| {
+            rects.push(flex.add(item().order(1), Button::new("A")).rect);
+            rects.push(flex.add(item(), Button::new("B")).rect);
+            rects.push(flex.add(item().order(-1), Button::new("C")).rect);
+        });
+
+        let response = Flex::horizontal_reverse().w_full().show(ui, |flex| {
+            rects.push(flex.add(item(), Button::new("A")).rect);
+            rects.push(flex.add(item(), Button::new("B")).rect);
+        });
+        rects.push(response.response.rect);
+
+        let response = Flex::vertical_reverse().height(100.0).show(ui, |flex| {
+            rects.push(flex.add(item(), Button::new("A")).rect);
+            rects.push(flex.add(item(), Button::new("B")).rect);
+        });
+        rects.push(response.response.rect);
+    });
+
+    harness.run();
+    should_be_stable(&mut harness);
+
+    let rects = rects.borrow();
+    // C, B, A
+    assert!(rects[2].max.x < rects[1].min.x && rects[1].max.x < rects[0].min.x);
+    // Right to left, starting at the right edge
+    assert!(rects[4].max.x < rects[3].min.x);
+    assert_eq!(rects[3].max.x, rects[5].max.x);
+    // Bottom to top, starting at the bottom of the container
+    assert!(rects[7].max.y < rects[6].min.y);
+    assert_eq!(rects[6].max.y, rects[8].max.y);
+    assert_eq!(rects[8].height(), 100.0);
+}
+
+#[test]
+fn absolute() {
+    let rects = RefCell::new(vec![]);
+
+    let mut harness = Harness::new_ui(|ui| {
+        let mut rects = rects.borrow_mut();
+        rects.clear();
+
+        let response = Flex::horizontal().show(ui, |flex| {
+            rects.push(flex.add(item(), Button::new("Inbox")).rect);
+            rects.push(
+                flex.add(
+                    item().absolute(Align2::RIGHT_TOP, [4.0, -4.0]),
+                    Label::new("3"),
+                )
+                .rect,
+            );
+        });
+        rects.push(response.response.rect);
+    });
+
+    harness.run();
+    should_be_stable(&mut harness);
+
+    let rects = rects.borrow();
+    // The badge doesn't take up space in the flex
+    assert_eq!(rects[2].width(), rects[0].width());
+    assert_eq!(
+        rects[1].right_top(),
+        rects[0].right_top() + Vec2::new(4.0, -4.0)
+    );
+}
+
+#[test]
+fn layout_cache() {
+    let text = Cell::new("Short");
+
+    let mut harness = Harness::new_ui(|ui| {
+        Flex::horizontal().w_full().show(ui, |flex| {
+            flex.add(item().grow(1.0), Button::new(text.get()));
+            flex.add_flex(item(), Flex::vertical(), |flex| {
+                flex.add(item(), Label::new("Nested"));
+            });
+        });
+    });
+
+    harness.run();
+
+    // Nothing changed, so both containers reuse their layout
+    LayoutCacheStats::reset(&harness.ctx);
+    harness.step();
+    assert_eq!(
+        LayoutCacheStats::get(&harness.ctx),
+        LayoutCacheStats { hits: 2, misses: 0 }
+    );
+
+    // The button size changed, so the outer container has to relayout
+    text.set("A lot longer text");
+    LayoutCacheStats::reset(&harness.ctx);
+    harness.run();
+    assert!(LayoutCacheStats::get(&harness.ctx).misses > 0);
+}
+
+#[rstest]
+fn breakpoints(#[values(400.0, 800.0)] width: f32) {
+    let rects = RefCell::new(vec![]);
+
+    let mut harness = Harness::builder().with_size([width, 300.0]).build_ui(|ui| {
+        let mut rects = rects.borrow_mut();
+        rects.clear();
+
+        Flex::vertical()
+            .at(
+                600.0,
+                FlexOverrides::new().direction(FlexDirection::Horizontal),
+            )
+            .show(ui, |flex| {
+                rects.push(flex.add(item(), Button::new("A")).rect);
+                rects.push(
+                    flex.add(
+                        item().at(
+                            Breakpoint::max_width(600.0),
+                            FlexItemOverrides::new().hidden(true),
+                        ),
+                        Button::new("B"),
+                    )
+                    .rect,
+                );
+                rects.push(flex.add(item(), Button::new("C")).rect);
+            });
+    });
+
+    harness.run();
+
+    let rects = rects.borrow();
+    if width > 600.0 {
+        // A, B and C in a row
+        assert!(rects[0].max.x < rects[1].min.x && rects[1].max.x < rects[2].min.x);
+        assert_eq!(rects[0].min.y, rects[2].min.y);
+    } else {
+        // B is hidden, A and C are stacked
+        assert!(rects[0].max.y < rects[2].min.y);
+        assert_eq!(rects[0].min.x, rects[2].min.x);
+    }
+}
+
+/// Paint a blue rect, so the animation tests can find where an item was painted
+fn paint_marker(ui: &mut Ui) {
+    let (rect, _) = ui.allocate_exact_size(Vec2::new(40.0, 20.0), Sense::hover());
+    ui.painter().rect_filled(rect, 0.0, Color32::BLUE);
+}
+
+/// The rect and alpha of the marker in the last frame, if it was painted
+fn find_marker(harness: &Harness) -> Option<(Rect, u8)> {
+    fn find(shape: &Shape) -> Option<(Rect, u8)> {
+        match shape {
+            Shape::Vec(shapes) => shapes.iter().find_map(find),
+            // Fading keeps the red and green channels at zero
+            Shape::Rect(rect) if rect.fill.r() == 0 && rect.fill.g() == 0 && rect.fill.b() > 0 => {
+                Some((rect.rect, rect.fill.a()))
+            }
+            _ => None,
+        }
+    }
+    harness
+        .output()
+        .shapes
+        .iter()
+        .find_map(|clipped| find(&clipped.shape))
+}
+
+#[test]
+fn animation() {
+    let show_first = Cell::new(false);
+    let rect = Cell::new(Rect::NOTHING);
+
+    let mut harness = Harness::builder().with_step_dt(0.05).build_ui(|ui| {
+        Flex::horizontal()
+            .animate(FlexAnimation::new().duration(0.25))
+            .show(ui, |flex| {
+                if show_first.get() {
+                    flex.add(item().content_id(Id::new("first")), Button::new("First"));
+                }
+                flex.add_ui(item().content_id(Id::new("second")), |ui| {
+                    rect.set(ui.max_rect());
+                    paint_marker(ui);
+                });
+            });
+    });
+
+    harness.run();
+    let start = find_marker(&harness).unwrap().0.min.x;
+
+    show_first.set(true);
+    harness.step();
+    // The item is laid out at its new rect right away, but still painted at the old one
+    let end = rect.get().min.x;
+    assert!(start < end);
+    assert_eq!(find_marker(&harness).unwrap().0.min.x, start);
+    harness.step();
+    let moving = find_marker(&harness).unwrap().0.min.x;
+    assert!(start < moving && moving < end);
+    assert_eq!(rect.get().min.x, end);
+
+    // The animation is done, so we don't need to repaint anymore
+    harness.run_steps(5);
+    harness.run();
+    assert_eq!(find_marker(&harness).unwrap().0.min.x, end);
+}
+
+#[test]
+fn animation_width_dependent_content() {
+    let show_first = Cell::new(false);
+
+    let mut harness = Harness::builder()
+        .with_step_dt(0.05)
+        .with_size(Vec2::new(300.0, 300.0))
+        .build_ui(|ui| {
+            Flex::horizontal()
+                .w_full()
+                .animate(FlexAnimation::new().duration(0.25))
+                .show(ui, |flex| {
+                    if show_first.get() {
+                        flex.add(item().content_id(Id::new("first")), Button::new("First"));
+                    }
+                    flex.add_ui(item().grow(1.0).content_id(Id::new("text")), |ui| {
+                        ui.add(Label::new("Some text that wraps if it gets narrower").wrap());
+                    });
+                });
+        });
+
+    harness.run();
+    show_first.set(true);
+    // The wrapping text is laid out at its final width, so the animation isn't restarted by
+    // its changing size and is done after its duration
+    harness.run_steps(6);
+    harness.run();
+}
+
+#[test]
+fn animation_exit() {
+    let show_removed = Cell::new(true);
+    let hide = Cell::new(false);
+
+    let mut harness = Harness::builder().with_step_dt(0.05).build_ui(|ui| {
+        Flex::vertical()
+            .animate(FlexAnimation::new().duration(0.25))
+            .show(ui, |flex| {
+                if show_removed.get() {
+                    flex.add_ui(item().content_id(Id::new("removed")), paint_marker);
+                }
+                flex.add(item().content_id(Id::new("other")), Button::new("Other"));
+            });
+    });
+
+    harness.run();
+    let (rect, alpha) = find_marker(&harness).unwrap();
+    assert_eq!(alpha, 255);
+
+    // Items that aren't added anymore fade out at their last position
+    show_removed.set(false);
+    harness.step();
+    harness.step();
+    let (faded_rect, faded_alpha) = find_marker(&harness).unwrap();
+    assert!(faded_alpha < 255);
+    assert_eq!(faded_rect.center(), rect.center());
+
+    harness.run_steps(5);
+    harness.run();
+    assert_eq!(find_marker(&harness), None);
+
+    // Hidden items fade out too, also without a content id
+    let mut harness = Harness::builder().with_step_dt(0.05).build_ui(|ui| {
+        Flex::vertical()
+            .animate(FlexAnimation::new().duration(0.25))
+            .show(ui, |flex| {
+                flex.add(item(), Button::new("Other"));
+                flex.add_ui(item().hidden(hide.get()), paint_marker);
+            });
+    });
+
+    harness.run();
+    hide.set(true);
+    harness.step();
+    harness.step();
+    assert!(find_marker(&harness).is_some_and(|(_, alpha)| alpha < 255));
+
+    harness.run_steps(5);
+    harness.run();
+    assert_eq!(find_marker(&harness), None);
+}
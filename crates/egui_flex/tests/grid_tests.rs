@@ -0,0 +1,72 @@
+use egui::{Button, Rect, Vec2};
+use egui_flex::{FlexGrid, GridTrack, item};
+use egui_kittest::Harness;
+use std::cell::RefCell;
+
+#[test]
+fn grid_tracks() {
+    let rects = RefCell::new(vec![]);
+
+    let mut harness = Harness::builder().with_size([600.0, 400.0]).build_ui(|ui| {
+        ui.spacing_mut().item_spacing = Vec2::splat(10.0);
+        let mut rects = rects.borrow_mut();
+        rects.clear();
+
+        FlexGrid::new()
+            .columns([
+                GridTrack::Points(100.0),
+                GridTrack::Fraction(1.0),
+                GridTrack::Fraction(3.0),
+                GridTrack::MinMax(20.0, 50.0),
+            ])
+            .width(500.0)
+            .show(ui, |grid| {
+                for idx in 0..4 {
+                    rects.push(grid.add(item(), Button::new(format!("{idx}"))).rect);
+                }
+            });
+    });
+
+    harness.run();
+
+    let rects = rects.borrow();
+    let widths: Vec<_> = rects.iter().map(Rect::width).collect();
+    // The min max track grows to its max, the fractions share the rest 1:3
+    assert_eq!(widths, [100.0, 80.0, 240.0, 50.0]);
+    assert_eq!(rects[1].min.x - rects[0].max.x, 10.0);
+    assert!(rects.iter().all(|rect| rect.min.y == rects[0].min.y));
+}
+
+#[test]
+fn grid_placement() {
+    let rects = RefCell::new(vec![]);
+
+    let mut harness = Harness::new_ui(|ui| {
+        ui.spacing_mut().item_spacing = Vec2::splat(10.0);
+        let mut rects = rects.borrow_mut();
+        rects.clear();
+
+        FlexGrid::new()
+            .columns([GridTrack::Points(50.0); 3])
+            .auto_rows(GridTrack::Points(20.0))
+            .show(ui, |grid| {
+                rects.push(grid.add(item().row_span(2), Button::new("tall")).rect);
+                rects.push(grid.add(item().column_span(2), Button::new("wide")).rect);
+                rects.push(grid.add(item(), Button::new("auto")).rect);
+                rects.push(
+                    grid.add(item().grid_row(3).grid_column(2), Button::new("fixed"))
+                        .rect,
+                );
+            });
+    });
+
+    harness.run();
+
+    let rects = rects.borrow();
+    assert_eq!(rects[0].size(), Vec2::new(50.0, 50.0));
+    assert_eq!(rects[1].size(), Vec2::new(110.0, 20.0));
+    assert_eq!(rects[1].min, rects[0].min + Vec2::new(60.0, 0.0));
+    // The first free cell is next to the tall item in the second row
+    assert_eq!(rects[2].min, rects[0].min + Vec2::new(60.0, 30.0));
+    assert_eq!(rects[3].min, rects[0].min + Vec2::new(120.0, 90.0));
+}
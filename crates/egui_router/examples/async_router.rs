@@ -39,6 +39,8 @@ async fn main() -> eframe::Result<()> {
                     })
                     .route("/", home)
                     .async_route("/post/{id}", post)
+                    // The transition starts once the user is loaded (or after the loader timeout)
+                    .loader_route("/user/{name}", load_user, user)
                     .default_path("/")
                     .build(&mut sender)
             });
@@ -48,7 +50,7 @@ async fn main() -> eframe::Result<()> {
                     router.navigate(&mut sender, route).ok();
                 }
                 RouterMessage::Back => {
-                    router.back().ok();
+                    router.back(&mut sender).ok();
                 }
             });
 
@@ -83,6 +85,14 @@ fn home() -> impl Route<AppState> {
                     .send(RouterMessage::Navigate("/post/error".to_string()))
                     .ok();
             }
+
+            ui.label("Navigate to user (loaded before the transition):");
+
+            if ui.link("User Lucas").clicked() {
+                inbox
+                    .send(RouterMessage::Navigate("/user/Lucas".to_string()))
+                    .ok();
+            }
         });
     }
 }
@@ -118,6 +128,29 @@ async fn post(request: OwnedRequest<AppState>) -> HandlerResult<impl Route<AppSt
     })
 }
 
+async fn load_user(request: OwnedRequest<AppState>) -> HandlerResult<String> {
+    let name = request
+        .params
+        .get("name")
+        .ok_or(HandlerError::NotFound)?
+        .clone();
+
+    tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+
+    Ok(name)
+}
+
+fn user(name: String) -> impl Route<AppState> {
+    move |ui: &mut Ui, sender: &mut AppState| {
+        background(ui, ui.style().visuals.extreme_bg_color, |ui| {
+            ui.heading(format!("User: {name}"));
+            if ui.button("back").clicked() {
+                sender.send(RouterMessage::Back).ok();
+            }
+        });
+    }
+}
+
 fn background(ui: &mut Ui, color: Color32, content: impl FnOnce(&mut Ui)) {
     Frame::NONE.fill(color).inner_margin(16.0).show(ui, |ui| {
         ui.set_width(ui.available_width());
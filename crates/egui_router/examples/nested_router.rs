@@ -0,0 +1,117 @@
+/// Example demonstrating nested routes.
+///
+/// The settings page renders its child routes in an [`Outlet`], navigating between the
+/// settings tabs only animates the outlet while the full path is still pushed to the history.
+use eframe::NativeOptions;
+use egui::{CentralPanel, Color32, Frame, Ui};
+use egui_inbox::UiInbox;
+use egui_router::{EguiRouter, Outlet, Request, Route, TransitionConfig};
+
+enum RouterMessage {
+    Navigate(String),
+    Back,
+}
+
+fn main() -> eframe::Result<()> {
+    let mut router: Option<(EguiRouter<UiInbox<RouterMessage>>, UiInbox<RouterMessage>)> = None;
+
+    eframe::run_ui_native(
+        "Nested Router Example",
+        NativeOptions::default(),
+        move |ui, _frame| {
+            let (router, inbox) = router.get_or_insert_with(|| {
+                let mut inbox = UiInbox::new();
+                let router = EguiRouter::builder()
+                    .transition(TransitionConfig::slide())
+                    .route("/", home)
+                    .nested("/settings", settings_layout, |children| {
+                        children
+                            .transition(TransitionConfig::fade())
+                            .route("/", || settings_tab("General"))
+                            .route("/profile", || settings_tab("Profile"))
+                            .route("/privacy", || settings_tab("Privacy"))
+                    })
+                    .default_path("/")
+                    .build(&mut inbox);
+                (router, inbox)
+            });
+
+            inbox.read(ui).for_each(|msg| match msg {
+                RouterMessage::Navigate(route) => {
+                    router.navigate(inbox, route).ok();
+                }
+                RouterMessage::Back => {
+                    router.back(inbox).ok();
+                }
+            });
+
+            CentralPanel::default().show(ui, |ui| {
+                ui.label(format!(
+                    "Path: {}",
+                    router.active_route().unwrap_or_default()
+                ));
+                router.ui(ui, inbox);
+            });
+        },
+    )
+}
+
+fn home() -> impl Route<UiInbox<RouterMessage>> {
+    |ui: &mut Ui, inbox: &mut UiInbox<RouterMessage>| {
+        background(ui, ui.style().visuals.faint_bg_color, |ui| {
+            ui.heading("Home");
+            if ui.link("Settings").clicked() {
+                inbox
+                    .sender()
+                    .send(RouterMessage::Navigate("/settings".to_string()))
+                    .ok();
+            }
+        });
+    }
+}
+
+fn settings_layout(
+    _req: Request<UiInbox<RouterMessage>>,
+    outlet: Outlet<UiInbox<RouterMessage>>,
+) -> impl Route<UiInbox<RouterMessage>> {
+    move |ui: &mut Ui, inbox: &mut UiInbox<RouterMessage>| {
+        background(ui, ui.style().visuals.window_fill, |ui| {
+            ui.heading("Settings");
+            ui.horizontal(|ui| {
+                for (label, path) in [
+                    ("General", "/settings"),
+                    ("Profile", "/settings/profile"),
+                    ("Privacy", "/settings/privacy"),
+                ] {
+                    if ui.button(label).clicked() {
+                        inbox
+                            .sender()
+                            .send(RouterMessage::Navigate(path.to_string()))
+                            .ok();
+                    }
+                }
+                if ui.button("Back").clicked() {
+                    inbox.sender().send(RouterMessage::Back).ok();
+                }
+            });
+            ui.separator();
+            outlet.ui(ui, inbox);
+        });
+    }
+}
+
+fn settings_tab(name: &'static str) -> impl Route<UiInbox<RouterMessage>> {
+    move |ui: &mut Ui, _: &mut UiInbox<RouterMessage>| {
+        background(ui, ui.style().visuals.extreme_bg_color, |ui| {
+            ui.label(format!("{name} settings"));
+        });
+    }
+}
+
+fn background(ui: &mut Ui, color: Color32, content: impl FnOnce(&mut Ui)) {
+    Frame::NONE.fill(color).inner_margin(16.0).show(ui, |ui| {
+        ui.set_width(ui.available_width());
+        ui.set_height(ui.available_height());
+        content(ui);
+    });
+}
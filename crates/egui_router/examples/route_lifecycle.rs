@@ -47,7 +47,7 @@ async fn main() -> eframe::Result<()> {
                     router.navigate(state, route).ok();
                 }
                 RouterMessage::Back => {
-                    router.back().ok();
+                    router.back(state).ok();
                 }
             });
 
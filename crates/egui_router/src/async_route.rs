@@ -5,6 +5,8 @@ use egui_suspense::EguiSuspense;
 
 pub(crate) struct AsyncRoute<State> {
     pub suspense: EguiSuspense<Box<dyn Route<State> + Send + Sync>, HandlerError>,
+    /// Should the router wait for the route to load before starting the transition?
+    pub wait_for_data: bool,
 }
 
 impl<State: 'static> AsyncRoute<State> {
@@ -47,4 +49,26 @@ impl<State: 'static> Route<State> for AsyncRoute<State> {
     fn enable_swipe(&self) -> Option<bool> {
         self.suspense.data().and_then(|route| route.enable_swipe())
     }
+
+    fn keep_alive(&self) -> bool {
+        self.suspense.data().is_some_and(|route| route.keep_alive())
+    }
+
+    fn on_evict(&mut self) {
+        if let Some(route) = self.suspense.data_mut() {
+            route.on_evict();
+        }
+    }
+
+    fn is_loading(&mut self, ctx: &egui::Context) -> bool {
+        if !self.wait_for_data {
+            return false;
+        }
+        self.suspense.poll(ctx);
+        if let Some(route) = self.suspense.data_mut() {
+            route.is_loading(ctx)
+        } else {
+            self.suspense.loading()
+        }
+    }
 }
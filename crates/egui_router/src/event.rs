@@ -0,0 +1,113 @@
+use crate::NavigationKind;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// A lifecycle callback of a [`crate::Route`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LifecycleEvent {
+    /// [`crate::Route::on_showing`]
+    Showing,
+    /// [`crate::Route::on_shown`]
+    Shown,
+    /// [`crate::Route::on_hiding`]
+    Hiding,
+    /// [`crate::Route::on_hide`]
+    Hide,
+}
+
+/// An event emitted by the router.
+/// Subscribe with [`crate::EguiRouter::subscribe`] or [`crate::RouterBuilder::on_event`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RouterEvent {
+    /// A navigation was completed (the transition might still be running)
+    Navigated {
+        /// The kind of navigation
+        kind: NavigationKind,
+        /// The path of the route that was active before the navigation
+        from: Option<String>,
+        /// The path of the route that is active now
+        to: Option<String>,
+        /// The history state index of the active route
+        state: u32,
+    },
+    /// A lifecycle callback of a route was called
+    Lifecycle {
+        /// The path of the route
+        path: String,
+        /// The id of the route instance, as shown by [`crate::EguiRouter::debug_ui`]
+        route_id: usize,
+        /// The callback that was called
+        event: LifecycleEvent,
+    },
+    /// A transition started
+    TransitionStarted {
+        /// The path of the route that is transitioned to
+        to: Option<String>,
+        /// Is this a backward transition?
+        backward: bool,
+    },
+    /// A transition finished
+    TransitionFinished {
+        /// The path of the route that was transitioned to
+        to: Option<String>,
+        /// Was this a backward transition?
+        backward: bool,
+    },
+    /// The handler of a route that was navigated to returned an error.
+    /// The route is shown with the [`crate::RouterBuilder::error_ui`].
+    HandlerFailed {
+        /// The path of the route
+        path: String,
+        /// The error returned by the handler
+        error: String,
+    },
+    /// A navigation that wasn't started by a method returning its error failed, e.g. the initial
+    /// navigation because a guard cancelled it or a navigation coming from the history because
+    /// the path doesn't match any route. The initial navigation falls back to the
+    /// [`crate::RouterBuilder::default_path`] and then to the [`crate::RouterBuilder::fallback`].
+    NavigationFailed {
+        /// The path that was navigated to
+        path: String,
+        /// The error
+        error: String,
+    },
+    /// The history reported a navigation, e.g. because the browser's back button was pressed.
+    /// The router handles it right after this event.
+    HistorySync {
+        /// The path reported by the history
+        path: String,
+        /// The state index reported by the history
+        state: Option<u32>,
+    },
+}
+
+type Listener = Box<dyn FnMut(&RouterEvent)>;
+
+/// The event listeners, shared between the router and the outlets of nested routes
+#[derive(Clone, Default)]
+pub(crate) struct Events(Rc<RefCell<Vec<Listener>>>);
+
+impl Events {
+    pub fn subscribe(&self, listener: impl FnMut(&RouterEvent) + 'static) {
+        self.0.borrow_mut().push(Box::new(listener));
+    }
+
+    /// Emit an event. The event is only created if there are any listeners.
+    ///
+    /// The listeners are taken out of the list while they run, so they can subscribe new
+    /// listeners or emit events themselves. Events emitted by a listener aren't passed to the
+    /// listeners that are currently running.
+    pub fn emit(&self, event: impl FnOnce() -> RouterEvent) {
+        let mut listeners = std::mem::take(&mut *self.0.borrow_mut());
+        if !listeners.is_empty() {
+            let event = event();
+            for listener in &mut listeners {
+                listener(&event);
+            }
+        }
+        // Keep the listeners that were subscribed while the listeners ran
+        let mut current = self.0.borrow_mut();
+        listeners.append(&mut current);
+        *current = listeners;
+    }
+}
@@ -0,0 +1,51 @@
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
+/// The kind of navigation a [`GuardRequest`] was created for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NavigationKind {
+    /// The initial navigation when the router is built
+    Initial,
+    /// A new route is pushed onto the history
+    Push,
+    /// The current route is replaced
+    Replace,
+    /// A back navigation (via [`crate::EguiRouter::back`], the history or the swipe-back gesture)
+    Back,
+    /// A forward navigation coming from the history (e.g. the browser's forward button)
+    Forward,
+}
+
+/// Request passed to a route guard
+pub struct GuardRequest<'a, State = ()> {
+    /// The path (including the query) that is being navigated to
+    pub path: &'a str,
+    /// The path (including the query) of the currently active route, if any
+    pub from: Option<&'a str>,
+    /// The parsed path params of the target route
+    pub params: matchit::Params<'a, 'a>,
+    /// The parsed query params of the target route
+    pub query: BTreeMap<Cow<'a, str>, Cow<'a, str>>,
+    /// The kind of navigation
+    pub kind: NavigationKind,
+    /// The custom state
+    pub state: &'a mut State,
+}
+
+/// The decision of a route guard
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GuardResult {
+    /// Let the navigation happen
+    Allow,
+    /// Cancel the navigation, the current route stays active.
+    /// [`crate::EguiRouter::navigate`], [`crate::EguiRouter::replace`] and
+    /// [`crate::EguiRouter::back`] return [`crate::RouterError::GuardRejected`], navigations
+    /// coming from the history are undone.
+    Cancel,
+    /// Cancel the navigation and navigate to the given path instead.
+    /// For push and forward navigations the redirect is pushed, for replace and back navigations
+    /// it replaces the current route, so the history doesn't grow.
+    Redirect(String),
+}
+
+pub(crate) type Guard<State> = Box<dyn FnMut(GuardRequest<State>) -> GuardResult>;
@@ -1,4 +1,4 @@
-use crate::{Request, Route};
+use crate::{Outlet, Request, Route};
 
 /// Error returned from a [Handler]
 #[derive(Debug, thiserror::Error)]
@@ -27,6 +27,9 @@ pub trait MakeHandler<State, Args> {
 pub(crate) type Handler<State> =
     Box<dyn FnMut(Request<State>) -> HandlerResult<Box<dyn Route<State>>>>;
 
+pub(crate) type LayoutHandler<State> =
+    Box<dyn FnMut(Request<State>, Outlet<State>) -> HandlerResult<Box<dyn Route<State>>>>;
+
 impl<F, State, R> MakeHandler<State, (Request<'static, State>, ())> for F
 where
     F: Fn(Request<State>) -> R,
@@ -1,34 +1,105 @@
 use crate::history::{History, HistoryEvent, HistoryResult};
 use egui::Context;
-use std::iter;
 
-/// A memory history implementation. Currently, this is a no-op, since [`EguiRouter`] stores the
-/// history itself, but this could change in the future.
+/// A memory history implementation with back and forward stacks, like a browser history.
+/// [`History::back`] and [`History::forward`] emit a [`HistoryEvent`] that the
+/// [`crate::EguiRouter`] picks up on the next frame, so [`crate::EguiRouter::forward`] works
+/// on native, too.
+/// To persist the history across restarts, use [`crate::EguiRouter::snapshot`].
 #[derive(Debug, Clone, Default)]
-pub struct MemoryHistory {}
+pub struct MemoryHistory {
+    entries: Vec<(String, u32)>,
+    index: usize,
+    events: Vec<HistoryEvent>,
+}
+
+impl MemoryHistory {
+    /// Create a new, empty memory history
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Is there an entry to go back to?
+    pub fn can_go_back(&self) -> bool {
+        self.index > 0
+    }
+
+    /// Is there an entry to go forward to?
+    pub fn can_go_forward(&self) -> bool {
+        self.index + 1 < self.entries.len()
+    }
+
+    /// Iterate over the paths of the entries before the current one (oldest first)
+    pub fn back_stack(&self) -> impl Iterator<Item = &str> {
+        self.entries[..self.index.min(self.entries.len())]
+            .iter()
+            .map(|(path, _)| path.as_str())
+    }
+
+    /// Iterate over the paths of the entries after the current one (closest first)
+    pub fn forward_stack(&self) -> impl Iterator<Item = &str> {
+        self.entries
+            .iter()
+            .skip(self.index + 1)
+            .map(|(path, _)| path.as_str())
+    }
+
+    fn emit_current(&mut self) {
+        if let Some((path, state)) = self.entries.get(self.index) {
+            self.events.push(HistoryEvent {
+                location: path.clone(),
+                state: Some(*state),
+            });
+        }
+    }
+}
 
 impl History for MemoryHistory {
     fn update(&mut self, _ctx: &Context) -> impl Iterator<Item = HistoryEvent> + 'static {
-        iter::empty()
+        std::mem::take(&mut self.events).into_iter()
     }
 
     fn active_route(&self) -> Option<(String, Option<u32>)> {
-        None
+        self.entries
+            .get(self.index)
+            .map(|(path, state)| (path.clone(), Some(*state)))
     }
 
-    fn push(&mut self, _url: &str, _state: u32) -> HistoryResult {
+    fn push(&mut self, url: &str, state: u32) -> HistoryResult {
+        if self.entries.is_empty() {
+            self.entries.push((url.to_string(), state));
+            self.index = 0;
+        } else {
+            self.entries.truncate(self.index + 1);
+            self.entries.push((url.to_string(), state));
+            self.index = self.entries.len() - 1;
+        }
         Ok(())
     }
 
-    fn replace(&mut self, _url: &str, _state: u32) -> HistoryResult {
+    fn replace(&mut self, url: &str, state: u32) -> HistoryResult {
+        if let Some(entry) = self.entries.get_mut(self.index) {
+            *entry = (url.to_string(), state);
+        } else {
+            self.entries.push((url.to_string(), state));
+            self.index = self.entries.len() - 1;
+        }
         Ok(())
     }
 
     fn back(&mut self) -> HistoryResult {
+        if self.can_go_back() {
+            self.index -= 1;
+            self.emit_current();
+        }
         Ok(())
     }
 
     fn forward(&mut self) -> HistoryResult {
+        if self.can_go_forward() {
+            self.index += 1;
+            self.emit_current();
+        }
         Ok(())
     }
 }
@@ -4,16 +4,26 @@
 
 #[cfg(feature = "async")]
 mod async_route;
+mod event;
+mod guard;
 mod handler;
 /// History types
 pub mod history;
+mod modal;
+mod outlet;
 mod route_kind;
+mod route_stack;
 mod router;
 mod router_builder;
 /// Transition types
 pub mod transition;
+/// Typed routes
+pub mod typed;
 
+use crate::event::Events;
 use crate::history::HistoryError;
+use crate::route_stack::RouteStack;
+use crate::router_builder::ErrorUi;
 use crate::transition::{ActiveTransition, SlideFadeTransition, SlideTransition, Transition};
 use egui::emath::ease_in_ease_out;
 use egui::{Ui, Vec2};
@@ -21,9 +31,17 @@ use std::borrow::Cow;
 use std::collections::BTreeMap;
 use std::sync::atomic::AtomicUsize;
 
+pub use event::{LifecycleEvent, RouterEvent};
+pub use guard::{GuardRequest, GuardResult, NavigationKind};
 pub use handler::{HandlerError, HandlerResult};
+pub use modal::ModalConfig;
+pub use outlet::Outlet;
 pub use router::EguiRouter;
 pub use router_builder::RouterBuilder;
+pub use typed::TypedRoute;
+
+#[cfg(feature = "derive")]
+pub use egui_router_derive::TypedRoute;
 
 /// A route instance created by a [`handler::Handler`]
 pub trait Route<State = ()> {
@@ -52,6 +70,26 @@ pub trait Route<State = ()> {
     fn enable_swipe(&self) -> Option<bool> {
         None
     }
+
+    /// Return `true` to keep this route alive after it's removed from the history (e.g. by a
+    /// back navigation or a replace). Navigating to the same path again revives the route
+    /// instead of calling the handler, so its state (like loaded data or scroll positions) is
+    /// kept. The number of cached routes is limited by [`RouterBuilder::keep_alive_limit`].
+    fn keep_alive(&self) -> bool {
+        false
+    }
+
+    /// Called when a kept alive route is evicted from the cache and dropped, either because
+    /// the cache is full or because a newer instance of the same path was cached.
+    fn on_evict(&mut self) {}
+
+    /// Return `true` while the route is still loading the data it needs.
+    /// When navigating to a loading route, the router keeps showing the previous route and
+    /// starts the transition once the route is done loading, or after the
+    /// [`RouterBuilder::loader_timeout`].
+    fn is_loading(&mut self, _ctx: &egui::Context) -> bool {
+        false
+    }
 }
 
 impl<F: FnMut(&mut Ui, &mut State), State> Route<State> for F {
@@ -67,6 +105,131 @@ struct RouteState<State> {
     route: HandlerResult<Box<dyn Route<State>>>,
     id: usize,
     state: u32,
+    /// The child routes, if this is the layout route of a nested route
+    outlet: Option<Outlet<State>>,
+    /// Set if this is a modal route, rendered on top of the route below
+    modal: Option<ModalConfig>,
+}
+
+impl<State> RouteState<State> {
+    fn ui(&mut self, ui: &mut Ui, state: &mut State, error_ui: &ErrorUi<State>) {
+        match &mut self.route {
+            Ok(route) => {
+                route.ui(ui, state);
+            }
+            Err(err) => {
+                error_ui(ui, state, err);
+            }
+        }
+    }
+
+    fn on_showing(&mut self) {
+        if let Ok(route) = &mut self.route {
+            route.on_showing();
+        }
+        if let Some(outlet) = &self.outlet {
+            outlet.with_active_child(RouteState::on_showing);
+        }
+    }
+
+    fn on_shown(&mut self) {
+        if let Ok(route) = &mut self.route {
+            route.on_shown();
+        }
+        if let Some(outlet) = &self.outlet {
+            outlet.with_active_child(RouteState::on_shown);
+        }
+    }
+
+    fn on_hiding(&mut self) {
+        if let Ok(route) = &mut self.route {
+            route.on_hiding();
+        }
+        if let Some(outlet) = &self.outlet {
+            outlet.with_active_child(RouteState::on_hiding);
+        }
+    }
+
+    fn on_hide(&mut self) {
+        if let Ok(route) = &mut self.route {
+            route.on_hide();
+        }
+        if let Some(outlet) = &self.outlet {
+            outlet.with_active_child(RouteState::on_hide);
+        }
+    }
+
+    /// Call the lifecycle callback and emit a [`RouterEvent::Lifecycle`]
+    fn lifecycle(&mut self, event: LifecycleEvent, events: &Events) {
+        match event {
+            LifecycleEvent::Showing => self.on_showing(),
+            LifecycleEvent::Shown => self.on_shown(),
+            LifecycleEvent::Hiding => self.on_hiding(),
+            LifecycleEvent::Hide => self.on_hide(),
+        }
+        events.emit(|| RouterEvent::Lifecycle {
+            path: self.path_with_query.clone(),
+            route_id: self.id,
+            event,
+        });
+    }
+
+    fn enable_swipe(&self) -> Option<bool> {
+        self.route
+            .as_ref()
+            .ok()
+            .and_then(|route| route.enable_swipe())
+    }
+
+    /// Prepare a kept alive route to be pushed again with a new history state index
+    fn revive(mut self, state: u32) -> Self {
+        self.state = state;
+        if let Some(outlet) = &self.outlet {
+            outlet.with_active_child(|child| child.state = state);
+        }
+        self
+    }
+
+    fn is_loading(&mut self, ctx: &egui::Context) -> bool {
+        let mut loading = self.route.as_mut().is_ok_and(|route| route.is_loading(ctx));
+        if let Some(outlet) = &self.outlet {
+            outlet.with_active_child(|child| loading |= child.is_loading(ctx));
+        }
+        loading
+    }
+
+    fn keep_alive(&self) -> bool {
+        self.route.as_ref().is_ok_and(|route| route.keep_alive())
+    }
+
+    fn on_evict(&mut self) {
+        if let Ok(route) = &mut self.route {
+            route.on_evict();
+        }
+        if let Some(outlet) = &self.outlet {
+            outlet.with_stack(RouteStack::evict_all);
+        }
+    }
+}
+
+/// A snapshot of the router's history, created by [`EguiRouter::snapshot`].
+/// With the `serde` feature enabled it can be persisted (e.g. via eframe's persistence) and
+/// restored with [`EguiRouter::restore`] or [`RouterBuilder::restore`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RouterSnapshot {
+    /// The history entries, oldest first
+    pub entries: Vec<SnapshotEntry>,
+}
+
+/// A single history entry of a [`RouterSnapshot`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SnapshotEntry {
+    /// The path, including the query
+    pub path: String,
+    /// The history state index
+    pub state: u32,
 }
 
 /// Router Result type
@@ -81,6 +244,14 @@ pub enum RouterError {
     /// Not found error
     #[error("Route not found")]
     NotFound,
+    /// Following the redirects (of redirect routes and guards) exceeded the
+    /// [`RouterBuilder::max_redirects`], most likely because they form a loop.
+    /// Contains the redirect that wasn't followed anymore.
+    #[error("Redirect loop detected at {0}")]
+    RedirectLoop(String),
+    /// A guard cancelled the navigation
+    #[error("Navigation rejected by a guard")]
+    GuardRejected,
 }
 
 impl From<HistoryError> for RouterError {
@@ -0,0 +1,81 @@
+use crate::TransitionConfig;
+use egui::{Align2, Color32};
+
+/// Configuration for a modal route, added with [`crate::RouterBuilder::modal_route`].
+///
+/// A modal route is shown on top of the route below it, which stays rendered.
+/// This is useful for deep-linkable dialogs and bottom sheets.
+#[derive(Debug, Clone)]
+pub struct ModalConfig {
+    pub(crate) align: Option<Align2>,
+    pub(crate) dim: Option<Color32>,
+    pub(crate) interactive_background: bool,
+    pub(crate) dismiss_on_click_outside: bool,
+    pub(crate) transition: TransitionConfig,
+    pub(crate) parent: Option<String>,
+}
+
+impl Default for ModalConfig {
+    fn default() -> Self {
+        Self {
+            align: Some(Align2::CENTER_CENTER),
+            dim: Some(Color32::from_black_alpha(128)),
+            interactive_background: false,
+            dismiss_on_click_outside: true,
+            transition: TransitionConfig::fade(),
+            parent: None,
+        }
+    }
+}
+
+impl ModalConfig {
+    /// Create a new modal config. By default, the modal is centered, the background is dimmed
+    /// and non-interactive, clicking outside dismisses the modal and it fades in and out.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set where the modal is placed in the router's area (default: [`Align2::CENTER_CENTER`]).
+    /// The modal is sized to fit its content, e.g. use [`Align2::CENTER_BOTTOM`] for a bottom
+    /// sheet. With `None`, the modal gets the whole area and has to place itself.
+    pub fn align(mut self, align: Option<Align2>) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// Set the color the route below is dimmed with, or `None` to not dim it
+    pub fn dim(mut self, dim: Option<Color32>) -> Self {
+        self.dim = dim;
+        self
+    }
+
+    /// Should the route below still react to the pointer? (default: false)
+    pub fn interactive_background(mut self, interactive: bool) -> Self {
+        self.interactive_background = interactive;
+        self
+    }
+
+    /// Should a click outside of the modal's content go back? (default: true)
+    /// The content is the area the modal route allocated in its [`egui::Ui`] (its `min_rect`),
+    /// so without [`ModalConfig::align`] make sure the route doesn't fill the whole space.
+    pub fn dismiss_on_click_outside(mut self, dismiss: bool) -> Self {
+        self.dismiss_on_click_outside = dismiss;
+        self
+    }
+
+    /// Set the transition used to show and dismiss the modal (default: [`TransitionConfig::fade`])
+    pub fn transition(mut self, transition: TransitionConfig) -> Self {
+        self.transition = transition;
+        self
+    }
+
+    /// Set the route that is shown below the modal when the modal is the initial route, e.g.
+    /// when it's opened with a deep link. The path params of the modal route can be used, e.g.
+    /// `/photos/{id}` for a modal at `/photos/{id}/share`.
+    /// By default, the closest parent path matching a route is used (`/photos/42` for
+    /// `/photos/42/share`).
+    pub fn parent(mut self, parent: impl Into<String>) -> Self {
+        self.parent = Some(parent.into());
+        self
+    }
+}
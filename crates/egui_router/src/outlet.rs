@@ -0,0 +1,107 @@
+use crate::route_stack::RouteStack;
+use crate::router_builder::ErrorUi;
+use crate::RouteState;
+use egui::Ui;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+pub(crate) struct OutletState<State> {
+    /// Index of the nested route definition this outlet belongs to
+    pub nested_id: usize,
+    /// The part of the path that matched the nested route prefix
+    pub base_path: String,
+    pub stack: RouteStack<State>,
+    pub error_ui: ErrorUi<State>,
+}
+
+/// Renders the child routes of a nested route.
+/// It's passed to the layout handler of [`crate::RouterBuilder::nested`], call [`Outlet::ui`]
+/// where the child routes should be shown.
+/// Navigations between child routes of the same nested route only animate inside the outlet.
+pub struct Outlet<State> {
+    pub(crate) inner: Rc<RefCell<OutletState<State>>>,
+}
+
+impl<State> Clone for Outlet<State> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<State> Outlet<State> {
+    pub(crate) fn new(
+        nested_id: usize,
+        base_path: String,
+        error_ui: ErrorUi<State>,
+        stack: RouteStack<State>,
+    ) -> Self {
+        Self {
+            inner: Rc::new(RefCell::new(OutletState {
+                nested_id,
+                base_path,
+                stack,
+                error_ui,
+            })),
+        }
+    }
+
+    /// Render the active child route
+    pub fn ui(&self, ui: &mut Ui, state: &mut State) {
+        let mut inner = self.inner.borrow_mut();
+        let OutletState {
+            stack, error_ui, ..
+        } = &mut *inner;
+        stack.show(ui, state, error_ui);
+    }
+
+    /// The path of the active child route
+    pub fn active_route(&self) -> Option<String> {
+        self.inner
+            .borrow()
+            .stack
+            .routes
+            .last()
+            .map(|r| r.path_with_query.clone())
+    }
+
+    /// How many child routes are in the outlet's history?
+    pub fn history_len(&self) -> usize {
+        self.inner.borrow().stack.routes.len()
+    }
+
+    /// The state index of the active child route
+    pub(crate) fn active_state(&self) -> Option<u32> {
+        self.inner.borrow().stack.routes.last().map(|r| r.state)
+    }
+
+    /// The path of the child route that would be revealed by going back inside the outlet
+    pub(crate) fn previous_route(&self) -> Option<String> {
+        let inner = self.inner.borrow();
+        let routes = &inner.stack.routes;
+        routes
+            .len()
+            .checked_sub(2)
+            .map(|idx| routes[idx].path_with_query.clone())
+    }
+
+    pub(crate) fn with_active_child(&self, f: impl FnOnce(&mut RouteState<State>)) {
+        if let Some(child) = self.inner.borrow_mut().stack.routes.last_mut() {
+            f(child);
+        }
+    }
+
+    pub(crate) fn with_stack<R>(&self, f: impl FnOnce(&mut RouteStack<State>) -> R) -> R {
+        f(&mut self.inner.borrow_mut().stack)
+    }
+
+    pub(crate) fn nested_id(&self) -> usize {
+        self.inner.borrow().nested_id
+    }
+
+    pub(crate) fn is_instance_of(&self, nested_id: usize, base_path: &str) -> bool {
+        let inner = self.inner.borrow();
+        inner.nested_id == nested_id && inner.base_path == base_path
+    }
+}
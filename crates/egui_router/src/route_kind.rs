@@ -1,6 +1,23 @@
-use crate::handler::Handler;
+use crate::handler::{Handler, LayoutHandler};
+use crate::{ModalConfig, TransitionConfig};
+
+/// Name of the catch-all param used to match the child path of a nested route
+pub(crate) const NESTED_PARAM: &str = "egui_router_nested";
 
 pub(crate) enum RouteKind<State> {
     Route(Handler<State>),
+    /// A route rendered on top of the route below it
+    Modal(Handler<State>, ModalConfig),
     Redirect(String),
+    /// Index into the nested route definitions of the router
+    Nested(usize),
+}
+
+/// A nested route definition, created by [`crate::RouterBuilder::nested`]
+pub(crate) struct NestedRoutes<State> {
+    pub layout: LayoutHandler<State>,
+    pub children: matchit::Router<RouteKind<State>>,
+    pub forward_transition: TransitionConfig,
+    pub backward_transition: TransitionConfig,
+    pub replace_transition: TransitionConfig,
 }
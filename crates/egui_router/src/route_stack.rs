@@ -0,0 +1,520 @@
+use crate::event::Events;
+use crate::router_builder::ErrorUi;
+use crate::transition::{ActiveTransition, ActiveTransitionResult};
+use crate::{CurrentTransition, RouteState};
+use crate::{LifecycleEvent, ModalConfig, RouterEvent};
+use egui::{Align2, Id, Rect, Sense, Ui, UiBuilder, Vec2};
+use std::collections::VecDeque;
+
+/// Routes that were removed from the stack but want to be kept alive, least recently used first
+pub(crate) struct RouteCache<State> {
+    limit: usize,
+    routes: VecDeque<RouteState<State>>,
+}
+
+impl<State> RouteCache<State> {
+    fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            routes: VecDeque::new(),
+        }
+    }
+
+    /// Cache the route if it wants to be kept alive, evicting the least recently used routes
+    /// if the cache is full. Other routes are dropped.
+    fn insert(&mut self, route: RouteState<State>) {
+        if self.limit == 0 || !route.keep_alive() {
+            return;
+        }
+        if let Some(mut old) = self.take(&route.path_with_query) {
+            old.on_evict();
+        }
+        self.routes.push_back(route);
+        while self.routes.len() > self.limit {
+            if let Some(mut evicted) = self.routes.pop_front() {
+                evicted.on_evict();
+            }
+        }
+    }
+
+    /// Remove and return the cached route for the path, if there is one
+    pub fn take(&mut self, path_with_query: &str) -> Option<RouteState<State>> {
+        let idx = self
+            .routes
+            .iter()
+            .position(|r| r.path_with_query == path_with_query)?;
+        self.routes.remove(idx)
+    }
+
+    fn evict_all(&mut self) {
+        for mut route in self.routes.drain(..) {
+            route.on_evict();
+        }
+    }
+}
+
+/// A stack of routes with an optional running transition.
+/// Used by the [`crate::EguiRouter`] itself and by the [`crate::Outlet`] of nested routes.
+pub(crate) struct RouteStack<State> {
+    pub routes: Vec<RouteState<State>>,
+    pub current_transition: Option<CurrentTransition<State>>,
+    pub cache: RouteCache<State>,
+    /// How long to wait for a loading route before starting the transition (in seconds)
+    loader_timeout: f32,
+    /// How long we have been waiting for the new top route to load
+    waiting: Option<f32>,
+    pub events: Events,
+}
+
+impl<State> RouteStack<State> {
+    pub fn new(keep_alive_limit: usize, loader_timeout: f32, events: Events) -> Self {
+        Self {
+            routes: Vec::new(),
+            current_transition: None,
+            cache: RouteCache::new(keep_alive_limit),
+            loader_timeout,
+            waiting: None,
+            events,
+        }
+    }
+
+    /// The path of the top route
+    fn top_path(&self) -> Option<String> {
+        self.routes.last().map(|r| r.path_with_query.clone())
+    }
+
+    /// Start a transition. The leaving route of an interrupted transition is cached or dropped.
+    pub fn set_transition(&mut self, transition: CurrentTransition<State>) {
+        let backward = transition.active_transition.is_backward();
+        self.events.emit(|| RouterEvent::TransitionStarted {
+            to: self.top_path(),
+            backward,
+        });
+        if let Some(leaving) = self
+            .current_transition
+            .replace(transition)
+            .and_then(|t| t.leaving_route)
+        {
+            self.cache.insert(leaving);
+        }
+    }
+
+    /// Remove the routes not matching the predicate, caching the ones that want to be kept alive
+    pub fn retain(&mut self, mut f: impl FnMut(&RouteState<State>) -> bool) {
+        let (kept, removed) = std::mem::take(&mut self.routes)
+            .into_iter()
+            .partition(|r| f(r));
+        self.routes = kept;
+        for route in removed {
+            self.cache.insert(route);
+        }
+    }
+
+    /// Remove all routes, caching the ones that want to be kept alive
+    pub fn clear(&mut self) {
+        self.retain(|_| false);
+        if let Some(leaving) = self.current_transition.take().and_then(|t| t.leaving_route) {
+            self.cache.insert(leaving);
+        }
+    }
+
+    /// Evict all cached routes and the kept alive routes in the stack.
+    /// Called when the route owning this stack (the layout route of a nested route) is evicted.
+    pub fn evict_all(&mut self) {
+        self.clear();
+        self.cache.evict_all();
+    }
+
+    /// Push a new route and animate it in
+    pub fn push(&mut self, route: RouteState<State>, transition: ActiveTransition) {
+        self.routes.push(route);
+
+        // Fire on_hiding on the previous top-of-stack (now second-to-last)
+        if self.routes.len() >= 2 {
+            let idx = self.routes.len() - 2;
+            self.routes[idx].lifecycle(LifecycleEvent::Hiding, &self.events);
+        }
+
+        // Fire on_showing on the newly created route
+        if let Some(last) = self.routes.last_mut() {
+            last.lifecycle(LifecycleEvent::Showing, &self.events);
+        }
+
+        self.set_transition(CurrentTransition {
+            active_transition: transition,
+            leaving_route: None,
+        });
+        self.waiting = Some(0.0);
+    }
+
+    /// Replace the top route and animate the new one in
+    pub fn replace(&mut self, route: RouteState<State>, transition: ActiveTransition) {
+        let mut leaving_route = self.routes.pop();
+
+        // Fire on_hiding on the leaving route
+        if let Some(leaving) = &mut leaving_route {
+            leaving.lifecycle(LifecycleEvent::Hiding, &self.events);
+        }
+
+        self.routes.push(route);
+
+        // Fire on_showing on the newly created route
+        if let Some(last) = self.routes.last_mut() {
+            last.lifecycle(LifecycleEvent::Showing, &self.events);
+        }
+
+        self.set_transition(CurrentTransition {
+            active_transition: transition,
+            leaving_route,
+        });
+        self.waiting = Some(0.0);
+    }
+
+    /// Pop the top route and animate it out. Returns false if there was nothing to go back to.
+    pub fn back(&mut self, transition: ActiveTransition) -> bool {
+        if self.routes.len() > 1 {
+            let mut leaving_route = self.routes.pop();
+
+            // Fire on_hiding on the leaving route
+            if let Some(leaving) = &mut leaving_route {
+                leaving.lifecycle(LifecycleEvent::Hiding, &self.events);
+            }
+
+            // Fire on_showing on the route that is now being revealed
+            if let Some(last) = self.routes.last_mut() {
+                last.lifecycle(LifecycleEvent::Showing, &self.events);
+            }
+
+            self.set_transition(CurrentTransition {
+                active_transition: transition,
+                leaving_route,
+            });
+            self.waiting = None;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Render the top route (and the leaving route, while a transition is running).
+    /// Returns true if a modal route was dismissed by a click outside of it.
+    pub fn show(&mut self, ui: &mut Ui, state: &mut State, error_ui: &ErrorUi<State>) -> bool {
+        if self.wait_for_loading_route(ui, state, error_ui) {
+            return false;
+        }
+
+        let leaving_is_modal = self
+            .current_transition
+            .as_ref()
+            .and_then(|t| t.leaving_route.as_ref())
+            .is_some_and(|r| r.modal.is_some());
+        let top_is_modal = self.routes.last().is_some_and(|r| r.modal.is_some());
+        if leaving_is_modal || top_is_modal {
+            return self.show_modal(ui, state, error_ui);
+        }
+
+        let Some((last, previous)) = self.routes.split_last_mut() else {
+            return false;
+        };
+
+        let result = if let Some(transition) = &mut self.current_transition {
+            let leaving_route_state = transition.leaving_route.as_mut().or(previous.last_mut());
+            Some(
+                transition.active_transition.show(
+                    ui,
+                    state,
+                    (last.id, |ui, state| last.ui(ui, state, error_ui)),
+                    leaving_route_state
+                        .map(|r| (r.id, |ui: &mut Ui, state: &mut _| r.ui(ui, state, error_ui))),
+                ),
+            )
+        } else {
+            ActiveTransition::show_default(ui, last.id, |ui| last.ui(ui, state, error_ui));
+            None
+        };
+
+        match result {
+            Some(ActiveTransitionResult::Done) => {
+                if let Some(transition) = self.current_transition.take() {
+                    self.finish_transition(transition);
+                }
+            }
+            Some(ActiveTransitionResult::Continue) | None => {}
+        }
+        false
+    }
+
+    /// Keep showing the previous route while the new top route is loading, until it's done
+    /// or the loader timeout is reached. Returns true while waiting.
+    fn wait_for_loading_route(
+        &mut self,
+        ui: &mut Ui,
+        state: &mut State,
+        error_ui: &ErrorUi<State>,
+    ) -> bool {
+        let Some(waited) = self.waiting else {
+            return false;
+        };
+        let loading = self
+            .routes
+            .last_mut()
+            .is_some_and(|route| route.is_loading(ui.ctx()));
+        if !loading || waited >= self.loader_timeout {
+            self.waiting = None;
+            return false;
+        }
+
+        self.waiting = Some(waited + ui.input(|input| input.stable_dt));
+        ui.ctx().request_repaint();
+
+        let leaving_route = self
+            .current_transition
+            .as_mut()
+            .and_then(|transition| transition.leaving_route.as_mut());
+        let len = self.routes.len();
+        let previous = match leaving_route {
+            Some(leaving_route) => Some(leaving_route),
+            None => len.checked_sub(2).and_then(|idx| self.routes.get_mut(idx)),
+        };
+        if let Some(previous) = previous {
+            ActiveTransition::show_default(ui, previous.id, |ui| {
+                previous.ui(ui, state, error_ui);
+            });
+        }
+        true
+    }
+
+    /// Render the routes while a modal is shown or animating.
+    /// The routes below the modal are rendered without a transition, only the modal is animated.
+    fn show_modal(&mut self, ui: &mut Ui, state: &mut State, error_ui: &ErrorUi<State>) -> bool {
+        let rect = ui.max_rect();
+        let transition = self.current_transition.as_ref();
+        // Only a fully shown modal can be dismissed by a click outside of it
+        let dismiss_modal = transition
+            .is_none()
+            .then(|| self.routes.last().and_then(|r| r.modal.clone()))
+            .flatten();
+        // The modal that is animating in or out, if any
+        let animated_is_leaving =
+            transition.is_some_and(|t| t.leaving_route.as_ref().is_some_and(|r| r.modal.is_some()));
+        let animated_is_top = !animated_is_leaving
+            && transition.is_some()
+            && self.routes.last().is_some_and(|r| r.modal.is_some());
+
+        // Render all routes from the last non-modal one up to (excluding) the animated one
+        let static_end = if animated_is_top {
+            self.routes.len() - 1
+        } else {
+            self.routes.len()
+        };
+        let static_start = self.routes[..static_end]
+            .iter()
+            .rposition(|r| r.modal.is_none())
+            .unwrap_or(0);
+        let mut content_rect = None;
+        for route in &mut self.routes[static_start..static_end] {
+            if let Some(modal) = &route.modal {
+                Self::modal_background(ui, rect, route.id, modal, 1.0);
+            }
+            let align = route.modal.as_ref().and_then(|modal| modal.align);
+            ActiveTransition::show_default(ui, route.id, |ui| {
+                content_rect = Some(Self::modal_content(ui, route.id, align, |ui| {
+                    route.ui(ui, state, error_ui);
+                }));
+            });
+        }
+
+        let mut result = None;
+        if let Some(transition) = &mut self.current_transition {
+            let progress = transition.active_transition.progress().clamp(0.0, 1.0);
+            let animated = if animated_is_leaving {
+                transition.leaving_route.as_mut()
+            } else if animated_is_top {
+                self.routes.last_mut()
+            } else {
+                None
+            };
+            if let Some(animated) = animated {
+                let modal = animated.modal.clone().unwrap_or_default();
+                let id = animated.id;
+                let t = if animated_is_leaving {
+                    1.0 - progress
+                } else {
+                    progress
+                };
+                Self::modal_background(ui, rect, animated.id, &modal, t);
+
+                let none = None::<(usize, fn(&mut Ui, &mut State))>;
+                result = Some(if animated_is_leaving {
+                    // Going back, the content_out is the leaving route
+                    transition.active_transition.show(
+                        ui,
+                        state,
+                        (0, |_: &mut Ui, _: &mut State| {}),
+                        Some((id, |ui: &mut Ui, state: &mut State| {
+                            Self::modal_content(ui, id, modal.align, |ui| {
+                                animated.ui(ui, state, error_ui);
+                            });
+                        })),
+                    )
+                } else {
+                    transition.active_transition.show(
+                        ui,
+                        state,
+                        (id, |ui: &mut Ui, state: &mut State| {
+                            Self::modal_content(ui, id, modal.align, |ui| {
+                                animated.ui(ui, state, error_ui);
+                            });
+                        }),
+                        none,
+                    )
+                });
+            } else {
+                result = Some(ActiveTransitionResult::Done);
+            }
+        }
+
+        if matches!(result, Some(ActiveTransitionResult::Done)) {
+            if let Some(transition) = self.current_transition.take() {
+                self.finish_transition(transition);
+            }
+        }
+
+        let clicked_outside = ui.input(|input| {
+            input.pointer.primary_clicked()
+                && input.pointer.interact_pos().is_some_and(|pos| {
+                    rect.contains(pos) && !content_rect.is_some_and(|r| r.contains(pos))
+                })
+        });
+        dismiss_modal.is_some_and(|modal| modal.dismiss_on_click_outside) && clicked_outside
+    }
+
+    /// Show the content of a route, placed by the modal's alignment.
+    /// The content is measured in an invisible sizing pass the first time it's shown.
+    /// Returns the rect of the content.
+    fn modal_content(
+        ui: &mut Ui,
+        id: usize,
+        align: Option<Align2>,
+        content: impl FnOnce(&mut Ui),
+    ) -> Rect {
+        let Some(align) = align else {
+            content(ui);
+            return ui.min_rect();
+        };
+
+        let size_id = Id::new("router_modal_size").with(id);
+        let size = ui.data(|data| data.get_temp::<Vec2>(size_id));
+        let builder = if let Some(size) = size {
+            UiBuilder::new().max_rect(align.align_size_within_rect(size, ui.max_rect()))
+        } else {
+            ui.ctx().request_repaint();
+            UiBuilder::new()
+                .max_rect(ui.max_rect())
+                .sizing_pass()
+                .invisible()
+        };
+        let mut child = ui.new_child(builder);
+        content(&mut child);
+        let rect = child.min_rect();
+        ui.data_mut(|data| data.insert_temp(size_id, rect.size()));
+        rect
+    }
+
+    /// Dim the routes below a modal and block their interactions
+    fn modal_background(ui: &mut Ui, rect: Rect, id: usize, modal: &ModalConfig, t: f32) {
+        if let Some(dim) = modal.dim {
+            ui.painter().rect_filled(rect, 0.0, dim.gamma_multiply(t));
+        }
+        if !modal.interactive_background {
+            ui.interact(
+                rect,
+                Id::new("router_modal_background").with(id),
+                Sense::click_and_drag(),
+            );
+        }
+    }
+
+    fn finish_transition(&mut self, mut transition: CurrentTransition<State>) {
+        let is_backward = transition.active_transition.is_backward();
+        if is_backward {
+            // Leaving route is fully hidden
+            if let Some(leaving) = &mut transition.leaving_route {
+                leaving.lifecycle(LifecycleEvent::Hide, &self.events);
+            }
+            // Current top is fully shown again
+            if let Some(last) = self.routes.last_mut() {
+                last.lifecycle(LifecycleEvent::Shown, &self.events);
+            }
+            if let Some(leaving) = transition.leaving_route {
+                self.cache.insert(leaving);
+            }
+        } else {
+            // Forward/replace completed
+            if let Some(leaving) = &mut transition.leaving_route {
+                // Replace: leaving route is fully hidden
+                leaving.lifecycle(LifecycleEvent::Hide, &self.events);
+            } else if self.routes.len() >= 2 {
+                // Forward: previous top is now fully hidden
+                let idx = self.routes.len() - 2;
+                self.routes[idx].lifecycle(LifecycleEvent::Hide, &self.events);
+            }
+            // The new top route is now fully shown
+            if let Some(last) = self.routes.last_mut() {
+                last.lifecycle(LifecycleEvent::Shown, &self.events);
+            }
+            if let Some(leaving) = transition.leaving_route {
+                self.cache.insert(leaving);
+            }
+        }
+        self.events.emit(|| RouterEvent::TransitionFinished {
+            to: self.top_path(),
+            backward: is_backward,
+        });
+    }
+
+    /// Show the routes with their state index, the running transition and the cached routes
+    pub fn debug_ui(&self, ui: &mut Ui) {
+        for route in &self.routes {
+            let modal = if route.modal.is_some() {
+                " (modal)"
+            } else {
+                ""
+            };
+            ui.monospace(format!(
+                "#{} {} [state {}]{modal}",
+                route.id, route.path_with_query, route.state
+            ));
+            if let Some(outlet) = &route.outlet {
+                ui.indent(route.id, |ui| outlet.with_stack(|stack| stack.debug_ui(ui)));
+            }
+        }
+
+        if let Some(transition) = &self.current_transition {
+            let direction = if transition.active_transition.is_backward() {
+                "backward"
+            } else {
+                "forward"
+            };
+            ui.label(format!(
+                "Transition: {direction}, {:.0}%",
+                transition.active_transition.progress() * 100.0
+            ));
+            if let Some(leaving) = &transition.leaving_route {
+                ui.monospace(format!(
+                    "Leaving: #{} {} [state {}]",
+                    leaving.id, leaving.path_with_query, leaving.state
+                ));
+            }
+        }
+        if let Some(waited) = self.waiting {
+            ui.label(format!("Waiting for the route to load ({waited:.2}s)"));
+        }
+        for route in &self.cache.routes {
+            ui.monospace(format!(
+                "Kept alive: #{} {}",
+                route.id, route.path_with_query
+            ));
+        }
+    }
+}
@@ -1,12 +1,16 @@
+use crate::event::Events;
+use crate::guard::{Guard, GuardRequest, GuardResult, NavigationKind};
+use crate::handler::Handler;
 use crate::history::{DefaultHistory, History};
-use crate::route_kind::RouteKind;
+use crate::route_kind::{NestedRoutes, RouteKind, NESTED_PARAM};
+use crate::route_stack::RouteStack;
 use crate::router_builder::{ErrorUi, RouterBuilder};
-use crate::transition::{ActiveTransition, ActiveTransitionResult};
+use crate::transition::ActiveTransition;
 use crate::{
-    CurrentTransition, Request, RouteState, RouterError, RouterResult, TransitionConfig, ID,
+    CurrentTransition, Outlet, Request, RouteState, RouterError, RouterEvent, RouterResult,
+    RouterSnapshot, SnapshotEntry, TransitionConfig, ID,
 };
 use egui::{scroll_area, Id, NumExt, Sense, Ui};
-use matchit::MatchError;
 use std::borrow::Cow;
 use std::collections::BTreeMap;
 use std::sync::atomic::Ordering;
@@ -25,10 +29,25 @@ enum SwipeBackGestureState {
     Cancelled,
 }
 
+/// The result of matching a path against the routes
+enum Resolved<State> {
+    /// A new top level route
+    Route(RouteState<State>),
+    /// A child route for the outlet of the active nested route
+    Child(RouteState<State>),
+    /// The path matched a redirect
+    Redirect(String),
+}
+
 /// A router instance
 pub struct EguiRouter<State, History = DefaultHistory> {
     router: matchit::Router<RouteKind<State>>,
-    history: Vec<RouteState<State>>,
+    nested: Vec<NestedRoutes<State>>,
+    fallback: Option<Handler<State>>,
+    stack: RouteStack<State>,
+
+    guards: Vec<Guard<State>>,
+    route_guards: matchit::Router<Vec<Guard<State>>>,
 
     history_kind: History,
 
@@ -36,11 +55,20 @@ pub struct EguiRouter<State, History = DefaultHistory> {
     backward_transition: TransitionConfig,
     replace_transition: TransitionConfig,
 
-    current_transition: Option<CurrentTransition<State>>,
     default_duration: Option<f32>,
+    max_redirects: usize,
 
     error_ui: ErrorUi<State>,
 
+    keep_alive_limit: usize,
+    loader_timeout: f32,
+
+    events: Events,
+
+    /// A guard redirected a back navigation coming from the history. The redirect replaces the
+    /// entry with this state index once the history went forward to it again.
+    pending_redirect: Option<(u32, String)>,
+
     /// Enable iOS-style swipe-to-go-back gesture
     swipe_back_gesture_enabled: bool,
     /// Minimum distance from left edge to start the gesture (in pixels)
@@ -58,50 +86,248 @@ impl<State: 'static, H: History + Default> EguiRouter<State, H> {
     pub(crate) fn from_builder(builder: RouterBuilder<State, H>, state: &mut State) -> Self {
         let mut router = Self {
             router: builder.router,
-            history: Vec::new(),
+            nested: builder.nested,
+            fallback: builder.fallback,
+            stack: RouteStack::new(
+                builder.keep_alive_limit,
+                builder.loader_timeout,
+                builder.events.clone(),
+            ),
+            guards: builder.guards,
+            route_guards: builder.route_guards,
             history_kind: builder.history_kind.unwrap_or_default(),
-            current_transition: None,
             forward_transition: builder.forward_transition,
             backward_transition: builder.backward_transition,
             replace_transition: builder.replace_transition,
             default_duration: builder.default_duration,
+            max_redirects: builder.max_redirects,
             error_ui: builder.error_ui,
+            keep_alive_limit: builder.keep_alive_limit,
+            loader_timeout: builder.loader_timeout,
+            events: builder.events,
+            pending_redirect: None,
             swipe_back_gesture_enabled: builder.swipe_back_gesture_enabled,
             swipe_back_edge_width: builder.swipe_back_edge_width,
             swipe_back_threshold: builder.swipe_back_threshold,
         };
 
-        if let Some((r, state_index)) = router
-            .history_kind
-            .active_route()
-            .or(builder.default_route.map(|d| (d, None)))
-        {
-            router
-                .navigate_impl(
-                    state,
-                    &r,
-                    TransitionConfig::none(),
-                    state_index.unwrap_or(0),
-                )
-                .unwrap();
+        if let Some(snapshot) = builder.snapshot {
+            router.restore(state, &snapshot).ok();
+            if !router.stack.routes.is_empty() {
+                return router;
+            }
+        }
+
+        let mut initial = router.history_kind.active_route();
+        if initial.is_none() {
+            if let Some(default_route) = &builder.default_route {
+                router.history_kind.replace(default_route, 0).ok();
+                initial = Some((default_route.clone(), None));
+            }
+        }
+
+        if let Some((path, state_index)) = initial {
+            let state_index = state_index.unwrap_or(0);
+            let mut result = router.navigate_initial(state, &path, state_index);
+
+            // Try the default path if the initial route was cancelled or its redirects loop
+            if let Err(error) = &result {
+                router.emit_navigation_failed(&path, error);
+                if let Some(default_route) = builder.default_route.filter(|r| *r != path) {
+                    router
+                        .history_kind
+                        .replace(&default_route, state_index)
+                        .ok();
+                    result = router.navigate_initial(state, &default_route, state_index);
+                    if let Err(error) = &result {
+                        router.emit_navigation_failed(&default_route, error);
+                    }
+                }
+            }
+
+            // Show the fallback route if there still is no route
+            if router.stack.routes.is_empty() {
+                if let Ok(Resolved::Route(route)) =
+                    Self::resolve_fallback(&mut router.fallback, state, &path, state_index)
+                {
+                    router.history_kind.replace(&path, state_index).ok();
+                    let transition = ActiveTransition::forward(TransitionConfig::none());
+                    router.stack.push(route, transition);
+                }
+            }
+        }
+
+        if !router.stack.routes.is_empty() {
+            router.emit_navigated(NavigationKind::Initial, None);
         }
 
         router
     }
 
-    /// Get the active route
+    /// Show the initial route, following the redirects of the guards
+    fn navigate_initial(
+        &mut self,
+        state: &mut State,
+        path: &str,
+        state_index: u32,
+    ) -> RouterResult {
+        let mut path = path.to_string();
+        let mut redirects = 0;
+        loop {
+            match self.check_guards(state, &path, NavigationKind::Initial) {
+                GuardResult::Allow => {
+                    let mut state_index = state_index;
+                    if self.navigate_modal_parent(state, &path, state_index) {
+                        state_index += 1;
+                        self.history_kind.push(&path, state_index)?;
+                    }
+                    return self.navigate_impl(
+                        state,
+                        &path,
+                        TransitionConfig::none(),
+                        state_index,
+                        redirects,
+                    );
+                }
+                GuardResult::Redirect(redirect) => {
+                    self.check_redirects(&redirect, redirects)?;
+                    self.history_kind.replace(&redirect, state_index)?;
+                    path = redirect;
+                    redirects += 1;
+                }
+                GuardResult::Cancel => return Err(RouterError::GuardRejected),
+            }
+        }
+    }
+
+    /// If the path is a modal route, show its parent route (see [`crate::ModalConfig::parent`])
+    /// below it, so the modal can be dismissed when it's opened with a deep link.
+    /// Returns whether the parent route is shown.
+    fn navigate_modal_parent(&mut self, state: &mut State, path: &str, state_index: u32) -> bool {
+        let Some(parent) = self.modal_parent(path) else {
+            return false;
+        };
+        if self.check_guards(state, &parent, NavigationKind::Initial) != GuardResult::Allow {
+            return false;
+        }
+        let shown = self
+            .navigate_impl(state, &parent, TransitionConfig::none(), state_index, 0)
+            .is_ok();
+        if shown {
+            self.history_kind.replace(&parent, state_index).ok();
+        }
+        shown
+    }
+
+    /// The parent route of a modal route, see [`crate::ModalConfig::parent`]
+    fn modal_parent(&self, path_with_query: &str) -> Option<String> {
+        let (path, _) = Self::parse_path(path_with_query);
+        let match_ = self.router.at(path).ok()?;
+        let RouteKind::Modal(_, config) = match_.value else {
+            return None;
+        };
+
+        if let Some(parent) = &config.parent {
+            let mut parent = parent.clone();
+            for (name, value) in match_.params.iter() {
+                parent = parent.replace(&format!("{{{name}}}"), value);
+            }
+            return Some(parent);
+        }
+
+        // The closest parent path that matches a route other than a modal
+        let mut parent = path.trim_end_matches('/');
+        while let Some((rest, _)) = parent.rsplit_once('/') {
+            parent = rest;
+            let candidate = if rest.is_empty() { "/" } else { rest };
+            let is_modal = self
+                .router
+                .at(candidate)
+                .is_ok_and(|match_| matches!(match_.value, RouteKind::Modal(..)));
+            if !is_modal && self.matches(candidate) {
+                return Some(candidate.to_string());
+            }
+        }
+        None
+    }
+
+    /// Emit a [`RouterEvent::NavigationFailed`] for a navigation that can't return its error
+    fn emit_navigation_failed(&self, path: &str, error: &RouterError) {
+        self.events.emit(|| RouterEvent::NavigationFailed {
+            path: path.to_string(),
+            error: error.to_string(),
+        });
+    }
+
+    /// Subscribe to the [`RouterEvent`]s, e.g. to log the navigations when debugging.
+    /// See also [`EguiRouter::debug_ui`].
+    ///
+    /// A listener subscribed while an event is emitted (e.g. by another listener) only receives
+    /// the events emitted after it.
+    pub fn subscribe(&mut self, listener: impl FnMut(&RouterEvent) + 'static) {
+        self.events.subscribe(listener);
+    }
+
+    /// Emit a [`RouterEvent::Navigated`] for the active route, followed by a
+    /// [`RouterEvent::HandlerFailed`] if a new route was shown and its handler failed
+    fn emit_navigated(&self, kind: NavigationKind, from: Option<String>) {
+        self.events.emit(|| RouterEvent::Navigated {
+            kind,
+            from,
+            to: self.active_route().map(ToOwned::to_owned),
+            state: self.active_state(),
+        });
+        if kind != NavigationKind::Back {
+            self.emit_handler_failed();
+        }
+    }
+
+    /// Show the router's internals: every route in the stack with its id and state index,
+    /// the child routes of nested routes, the running transition and the kept alive routes.
+    pub fn debug_ui(&self, ui: &mut Ui) {
+        ui.label(format!(
+            "Active route: {}",
+            self.active_route().unwrap_or("none")
+        ));
+        ui.label(format!("Active state index: {}", self.active_state()));
+        ui.separator();
+        self.stack.debug_ui(ui);
+    }
+
+    /// Show [`EguiRouter::debug_ui`] in a window
+    pub fn debug_window(&self, ctx: &egui::Context, open: &mut bool) {
+        egui::Window::new("Router")
+            .id(Id::new("egui_router_debug_window"))
+            .open(open)
+            .show(ctx, |ui| self.debug_ui(ui));
+    }
+
+    /// Get the active route. For nested routes, this is the full path of the active child route.
     pub fn active_route(&self) -> Option<&str> {
-        self.history.last().map(|r| r.path_with_query.as_str())
+        self.stack.routes.last().map(|r| r.path_with_query.as_str())
     }
 
     /// How many history entries are there?
+    /// Child routes of a nested route are tracked by its [`Outlet`] and not counted here.
     pub fn history_len(&self) -> usize {
-        self.history.len()
+        self.stack.routes.len()
     }
 
     /// Iterate over the paths in the history
     pub fn history(&self) -> impl Iterator<Item = &str> {
-        self.history.iter().map(|s| s.path_with_query.as_str())
+        self.stack.routes.iter().map(|s| s.path_with_query.as_str())
+    }
+
+    /// The [`History`] implementation
+    pub fn history_kind(&self) -> &H {
+        &self.history_kind
+    }
+
+    /// The [`History`] implementation. Navigations done with it directly (e.g.
+    /// [`History::back`]) are handled during the next [`EguiRouter::ui`] call, like a click on
+    /// the browser's back button.
+    pub fn history_kind_mut(&mut self) -> &mut H {
+        &mut self.history_kind
     }
 
     fn parse_path(path: &str) -> (&str, BTreeMap<Cow<'_, str>, Cow<'_, str>>) {
@@ -110,206 +336,602 @@ impl<State: 'static, H: History + Default> EguiRouter<State, H> {
             .unwrap_or((path, BTreeMap::new()))
     }
 
-    fn navigate_impl(
+    /// The outlet of the active route, if it is a nested route
+    fn active_outlet(&self) -> Option<&Outlet<State>> {
+        self.stack.routes.last().and_then(|r| r.outlet.as_ref())
+    }
+
+    /// The state index of the active history entry
+    fn active_state(&self) -> u32 {
+        self.stack.routes.last().map_or(0, |r| {
+            r.outlet
+                .as_ref()
+                .and_then(Outlet::active_state)
+                .unwrap_or(r.state)
+        })
+    }
+
+    /// Returns [`RouterError::RedirectLoop`] if following another redirect would exceed the
+    /// [`RouterBuilder::max_redirects`]
+    fn check_redirects(&self, redirect: &str, redirects: usize) -> RouterResult {
+        if redirects >= self.max_redirects {
+            Err(RouterError::RedirectLoop(redirect.to_string()))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Emit a [`RouterEvent::HandlerFailed`] if the handler of the active route (or the active
+    /// child route of a nested route) returned an error
+    fn emit_handler_failed(&self) {
+        let Some(top) = self.stack.routes.last() else {
+            return;
+        };
+        let mut error = top.route.as_ref().err().map(ToString::to_string);
+        if let Some(outlet) = &top.outlet {
+            outlet.with_active_child(|child| {
+                if let Err(err) = &child.route {
+                    error = Some(err.to_string());
+                }
+            });
+        }
+        if let Some(error) = error {
+            self.events.emit(|| RouterEvent::HandlerFailed {
+                path: top.path_with_query.clone(),
+                error,
+            });
+        }
+    }
+
+    /// Run the global guards and the guards matching `path_with_query`.
+    /// Returns the result of the first guard that doesn't allow the navigation.
+    fn check_guards(
         &mut self,
         state: &mut State,
         path_with_query: &str,
-        transition_config: TransitionConfig,
+        kind: NavigationKind,
+    ) -> GuardResult {
+        let (path, query) = Self::parse_path(path_with_query);
+        let params = self
+            .router
+            .at(path)
+            .map(|match_| match_.params)
+            .unwrap_or_default();
+        let from = self.stack.routes.last().map(|r| r.path_with_query.as_str());
+
+        let route_guards = self
+            .route_guards
+            .at_mut(path)
+            .map(|match_| match_.value)
+            .ok();
+
+        for guard in self.guards.iter_mut().chain(
+            route_guards
+                .into_iter()
+                .flat_map(|guards| guards.iter_mut()),
+        ) {
+            let result = guard(GuardRequest {
+                path: path_with_query,
+                from,
+                params: params.clone(),
+                query: query.clone(),
+                kind,
+                state: &mut *state,
+            });
+            if result != GuardResult::Allow {
+                return result;
+            }
+        }
+
+        GuardResult::Allow
+    }
+
+    /// The path of the route that would be revealed by a back navigation
+    fn back_target(&self) -> Option<String> {
+        if let Some(previous) = self.active_outlet().and_then(Outlet::previous_route) {
+            return Some(previous);
+        }
+        self.stack
+            .routes
+            .len()
+            .checked_sub(2)
+            .map(|idx| self.stack.routes[idx].path_with_query.clone())
+    }
+
+    /// Check whether the path matches a route, without calling any handlers
+    fn matches(&self, path_with_query: &str) -> bool {
+        let (path, _) = Self::parse_path(path_with_query);
+        match self.router.at(path) {
+            Ok(match_) => match match_.value {
+                RouteKind::Nested(nested_id) => {
+                    let rest = match_.params.get(NESTED_PARAM).unwrap_or_default();
+                    self.nested[*nested_id]
+                        .children
+                        .at(&format!("/{rest}"))
+                        .is_ok()
+                }
+                RouteKind::Route(_) | RouteKind::Modal(..) | RouteKind::Redirect(_) => true,
+            },
+            Err(_) => false,
+        }
+    }
+
+    /// Create the fallback route for a path that doesn't match any route.
+    /// Returns [`RouterError::NotFound`] if there is no fallback route.
+    fn resolve_fallback(
+        fallback: &mut Option<Handler<State>>,
+        state: &mut State,
+        path_with_query: &str,
         new_state: u32,
-    ) -> RouterResult {
+    ) -> RouterResult<Resolved<State>> {
+        let fallback = fallback.as_mut().ok_or(RouterError::NotFound)?;
+        let (_, query) = Self::parse_path(path_with_query);
+        let route = fallback(Request {
+            state,
+            params: matchit::Params::new(),
+            query,
+        });
+        Ok(Resolved::Route(RouteState {
+            path_with_query: path_with_query.to_string(),
+            route,
+            id: ID.fetch_add(1, Ordering::SeqCst),
+            state: new_state,
+            outlet: None,
+            modal: None,
+        }))
+    }
+
+    /// Match the path and call the handler(s).
+    /// Kept alive routes for the path are revived instead of calling the handler.
+    #[allow(clippy::too_many_lines)]
+    fn resolve(
+        &mut self,
+        state: &mut State,
+        path_with_query: &str,
+        new_state: u32,
+    ) -> RouterResult<Resolved<State>> {
         let (path, query) = Self::parse_path(path_with_query);
 
-        let mut redirect = None;
-        let result = self.router.at_mut(path);
+        let Ok(match_) = self.router.at_mut(path) else {
+            return Self::resolve_fallback(&mut self.fallback, state, path_with_query, new_state);
+        };
 
-        let result = match result {
-            Ok(match_) => {
-                match match_.value {
-                    RouteKind::Route(handler) => {
-                        let route = handler(Request {
-                            state,
-                            params: match_.params,
-                            query,
-                        });
-                        self.history.push(RouteState {
-                            path_with_query: path_with_query.to_string(),
-                            route,
-                            id: ID.fetch_add(1, Ordering::SeqCst),
-                            state: new_state,
-                        });
-
-                        // Fire on_hiding on the previous top-of-stack (now second-to-last)
-                        if self.history.len() >= 2 {
-                            let idx = self.history.len() - 2;
-                            if let Ok(route) = &mut self.history[idx].route {
-                                route.on_hiding();
-                            }
-                        }
+        let modal = match &*match_.value {
+            RouteKind::Modal(_, config) => Some(config.clone()),
+            _ => None,
+        };
 
-                        // Fire on_showing on the newly created route
-                        if let Some(last) = self.history.last_mut() {
-                            if let Ok(route) = &mut last.route {
-                                route.on_showing();
-                            }
-                        }
+        match match_.value {
+            RouteKind::Route(handler) | RouteKind::Modal(handler, _) => {
+                if let Some(cached) = self.stack.cache.take(path_with_query) {
+                    return Ok(Resolved::Route(cached.revive(new_state)));
+                }
+                let route = handler(Request {
+                    state,
+                    params: match_.params,
+                    query,
+                });
+                Ok(Resolved::Route(RouteState {
+                    path_with_query: path_with_query.to_string(),
+                    route,
+                    id: ID.fetch_add(1, Ordering::SeqCst),
+                    state: new_state,
+                    outlet: None,
+                    modal,
+                }))
+            }
+            RouteKind::Redirect(r) => Ok(Resolved::Redirect(r.clone())),
+            RouteKind::Nested(nested_id) => {
+                let nested_id = *nested_id;
+                let rest = match_.params.get(NESTED_PARAM).unwrap_or_default();
+                let base_path = path[..path.len() - rest.len()]
+                    .trim_end_matches('/')
+                    .to_string();
+                let child_path = format!("/{rest}");
+
+                let active_outlet = self.stack.routes.last().and_then(|r| r.outlet.clone());
+                let is_active_instance = active_outlet
+                    .as_ref()
+                    .is_some_and(|outlet| outlet.is_instance_of(nested_id, &base_path));
+                let cached = if is_active_instance {
+                    active_outlet.and_then(|outlet| {
+                        outlet.with_stack(|stack| stack.cache.take(path_with_query))
+                    })
+                } else {
+                    self.stack.cache.take(path_with_query)
+                };
+                if let Some(cached) = cached.map(|cached| cached.revive(new_state)) {
+                    return Ok(if is_active_instance {
+                        Resolved::Child(cached)
+                    } else {
+                        Resolved::Route(cached)
+                    });
+                }
 
-                        self.current_transition = Some(CurrentTransition {
-                            active_transition: ActiveTransition::forward(transition_config.clone())
-                                .with_default_duration(self.default_duration),
-                            leaving_route: None,
-                        });
-                    }
-                    RouteKind::Redirect(r) => {
-                        redirect = Some(r.clone());
+                let nested = &mut self.nested[nested_id];
+                let Ok(child_match) = nested.children.at_mut(&child_path) else {
+                    return Self::resolve_fallback(
+                        &mut self.fallback,
+                        state,
+                        path_with_query,
+                        new_state,
+                    );
+                };
+                let child_route = match child_match.value {
+                    RouteKind::Route(handler) | RouteKind::Modal(handler, _) => handler(Request {
+                        state: &mut *state,
+                        params: child_match.params,
+                        query: query.clone(),
+                    }),
+                    RouteKind::Redirect(r) => return Ok(Resolved::Redirect(r.clone())),
+                    RouteKind::Nested(_) => {
+                        return Self::resolve_fallback(
+                            &mut self.fallback,
+                            state,
+                            path_with_query,
+                            new_state,
+                        );
                     }
+                };
+                let child = RouteState {
+                    path_with_query: path_with_query.to_string(),
+                    route: child_route,
+                    id: ID.fetch_add(1, Ordering::SeqCst),
+                    state: new_state,
+                    outlet: None,
+                    modal: None,
+                };
+
+                if is_active_instance {
+                    return Ok(Resolved::Child(child));
                 }
-                Ok(())
+
+                let outlet = Outlet::new(
+                    nested_id,
+                    base_path,
+                    self.error_ui.clone(),
+                    RouteStack::new(
+                        self.keep_alive_limit,
+                        self.loader_timeout,
+                        self.events.clone(),
+                    ),
+                );
+                outlet.with_stack(|stack| stack.routes.push(child));
+                let route = (nested.layout)(
+                    Request {
+                        state,
+                        params: match_.params,
+                        query,
+                    },
+                    outlet.clone(),
+                );
+                Ok(Resolved::Route(RouteState {
+                    path_with_query: path_with_query.to_string(),
+                    route,
+                    id: ID.fetch_add(1, Ordering::SeqCst),
+                    state: new_state,
+                    outlet: Some(outlet),
+                    modal: None,
+                }))
             }
-            Err(e) => match e {
-                MatchError::NotFound => Err(RouterError::NotFound),
-            },
-        };
+        }
+    }
 
-        if let Some(redirect) = redirect {
-            self.history_kind.replace(&redirect, new_state)?;
-            self.navigate_impl(state, &redirect, transition_config, new_state)?;
+    /// Show the route for the path. `redirects` is the number of redirects that were already
+    /// followed for this navigation.
+    fn navigate_impl(
+        &mut self,
+        state: &mut State,
+        path_with_query: &str,
+        transition_config: TransitionConfig,
+        new_state: u32,
+        redirects: usize,
+    ) -> RouterResult {
+        match self.resolve(state, path_with_query, new_state)? {
+            Resolved::Route(route) => {
+                let transition_config = route
+                    .modal
+                    .as_ref()
+                    .map_or(transition_config, |modal| modal.transition.clone());
+                let transition = ActiveTransition::forward(transition_config)
+                    .with_default_duration(self.default_duration);
+                self.stack.push(route, transition);
+            }
+            Resolved::Child(route) => {
+                if let Some(top) = self.stack.routes.last_mut() {
+                    top.path_with_query.clone_from(&route.path_with_query);
+                    if let Some(outlet) = &top.outlet {
+                        let transition = ActiveTransition::forward(
+                            self.nested[outlet.nested_id()].forward_transition.clone(),
+                        )
+                        .with_default_duration(self.default_duration);
+                        outlet.with_stack(|stack| stack.push(route, transition));
+                    }
+                }
+            }
+            Resolved::Redirect(redirect) => {
+                self.check_redirects(&redirect, redirects)?;
+                self.history_kind.replace(&redirect, new_state)?;
+                self.navigate_impl(
+                    state,
+                    &redirect,
+                    transition_config,
+                    new_state,
+                    redirects + 1,
+                )?;
+            }
         }
 
-        result
+        Ok(())
     }
 
-    /// Navigate with a custom transition
+    /// Navigate with a custom transition.
+    ///
+    /// Returns an error if the path doesn't match any route (and there is no
+    /// [`RouterBuilder::fallback`]), if a guard cancelled the navigation or if the redirects form
+    /// a loop. If the route's handler failed, the route is still shown with the
+    /// [`RouterBuilder::error_ui`] and a [`RouterEvent::HandlerFailed`] is emitted.
     pub fn navigate_transition(
         &mut self,
         state: &mut State,
         path: impl Into<String>,
         transition_config: TransitionConfig,
     ) -> RouterResult {
-        let path = path.into();
-        let current_state = self.history.last().map_or(0, |r| r.state);
-        let new_state = current_state + 1;
-        self.history_kind.push(&path, new_state)?;
-        self.navigate_impl(state, &path, transition_config, new_state)?;
+        self.navigate_with_redirects(state, &path.into(), transition_config, 0)
+    }
+
+    fn navigate_with_redirects(
+        &mut self,
+        state: &mut State,
+        path: &str,
+        transition_config: TransitionConfig,
+        redirects: usize,
+    ) -> RouterResult {
+        match self.check_guards(state, path, NavigationKind::Push) {
+            GuardResult::Allow => {}
+            GuardResult::Cancel => return Err(RouterError::GuardRejected),
+            GuardResult::Redirect(redirect) => {
+                self.check_redirects(&redirect, redirects)?;
+                return self.navigate_with_redirects(
+                    state,
+                    &redirect,
+                    transition_config,
+                    redirects + 1,
+                );
+            }
+        }
+
+        let from = self.active_route().map(ToOwned::to_owned);
+        let new_state = self.active_state() + 1;
+        self.history_kind.push(path, new_state)?;
+        self.navigate_impl(state, path, transition_config, new_state, redirects)?;
+        self.emit_navigated(NavigationKind::Push, from);
         Ok(())
     }
 
-    /// Navigate with the default transition
+    /// Navigate with the default transition, see [`EguiRouter::navigate_transition`]
     pub fn navigate(&mut self, state: &mut State, route: impl Into<String>) -> RouterResult {
         self.navigate_transition(state, route, self.forward_transition.clone())
     }
 
     fn back_impl(&mut self, transition_config: TransitionConfig) {
-        if self.history.len() > 1 {
-            let mut leaving_route = self.history.pop();
-
-            // Fire on_hiding on the leaving route
-            if let Some(ref mut leaving) = leaving_route {
-                if let Ok(route) = &mut leaving.route {
-                    route.on_hiding();
+        // Go back inside the outlet of the active route, if it has any child history
+        if let Some(top) = self.stack.routes.last_mut() {
+            if let Some(outlet) = &top.outlet {
+                if outlet.history_len() > 1 {
+                    let transition = ActiveTransition::backward(
+                        self.nested[outlet.nested_id()].backward_transition.clone(),
+                    )
+                    .with_default_duration(self.default_duration);
+                    outlet.with_stack(|stack| stack.back(transition));
+                    if let Some(path) = outlet.active_route() {
+                        top.path_with_query = path;
+                    }
+                    return;
                 }
             }
+        }
+
+        let transition = ActiveTransition::backward(self.modal_transition(transition_config))
+            .with_default_duration(self.default_duration);
+        self.stack.back(transition);
+    }
 
-            // Fire on_showing on the route that is now being revealed
-            if let Some(last) = self.history.last_mut() {
-                if let Ok(route) = &mut last.route {
-                    route.on_showing();
+    /// The transition of the active route if it's a modal, `transition_config` otherwise
+    fn modal_transition(&self, transition_config: TransitionConfig) -> TransitionConfig {
+        self.stack
+            .routes
+            .last()
+            .and_then(|r| r.modal.as_ref())
+            .map_or(transition_config, |modal| modal.transition.clone())
+    }
+
+    /// Go back with a custom transition.
+    /// The `state` is passed to the guards, see [`RouterBuilder::guard`].
+    /// Returns [`RouterError::GuardRejected`] if a guard cancelled the navigation.
+    /// If a guard redirected it, the redirect replaces the current route.
+    pub fn back_transition(
+        &mut self,
+        state: &mut State,
+        transition_config: TransitionConfig,
+    ) -> RouterResult {
+        if let Some(target) = self.back_target() {
+            match self.check_guards(state, &target, NavigationKind::Back) {
+                GuardResult::Allow => {}
+                GuardResult::Cancel => return Err(RouterError::GuardRejected),
+                GuardResult::Redirect(redirect) => {
+                    let transition_config = self.replace_transition.clone();
+                    return self.replace_with_redirects(state, &redirect, transition_config, 1);
                 }
             }
-
-            self.current_transition = Some(CurrentTransition {
-                active_transition: ActiveTransition::backward(transition_config)
-                    .with_default_duration(self.default_duration),
-                leaving_route,
-            });
         }
-    }
 
-    /// Go back with a custom transition
-    pub fn back_transition(&mut self, transition_config: TransitionConfig) -> RouterResult {
+        let from = self.active_route().map(ToOwned::to_owned);
         self.history_kind.back()?;
         self.back_impl(transition_config);
+        self.emit_navigated(NavigationKind::Back, from);
         Ok(())
     }
 
     /// Go back with the default transition
-    pub fn back(&mut self) -> RouterResult {
-        self.back_transition(self.backward_transition.clone())
+    pub fn back(&mut self, state: &mut State) -> RouterResult {
+        self.back_transition(state, self.backward_transition.clone())
     }
 
-    /// Replace the current route with a custom transition
-    pub fn replace_transition(
-        &mut self,
-        state: &mut State,
-        path: impl Into<String>,
-        transition_config: TransitionConfig,
-    ) -> RouterResult {
-        let mut redirect = None;
+    /// Go forward in the history, if there is an entry to go forward to.
+    /// The navigation happens once the history reports it, during the next [`EguiRouter::ui`] call.
+    pub fn forward(&mut self) -> RouterResult {
+        self.history_kind.forward()?;
+        Ok(())
+    }
 
-        let path_with_query = path.into();
-        let (path, query) = Self::parse_path(&path_with_query);
+    /// Create a snapshot of the history, e.g. to persist it across app restarts.
+    /// Child routes of nested routes are included.
+    pub fn snapshot(&self) -> RouterSnapshot {
+        let mut entries = Vec::new();
+        for route in &self.stack.routes {
+            if let Some(outlet) = &route.outlet {
+                outlet.with_stack(|stack| {
+                    entries.extend(stack.routes.iter().map(|child| SnapshotEntry {
+                        path: child.path_with_query.clone(),
+                        state: child.state,
+                    }));
+                });
+            } else {
+                entries.push(SnapshotEntry {
+                    path: route.path_with_query.clone(),
+                    state: route.state,
+                });
+            }
+        }
+        RouterSnapshot { entries }
+    }
 
-        let result = self.router.at_mut(path);
+    /// Restore a [`RouterSnapshot`] created by [`EguiRouter::snapshot`].
+    /// The current history is discarded and the route handlers are called for every entry, in order.
+    /// Guards run for every entry with [`NavigationKind::Initial`]. Restoring stops at the first
+    /// entry that doesn't match a route or that is cancelled or redirected by a guard.
+    pub fn restore(&mut self, state: &mut State, snapshot: &RouterSnapshot) -> RouterResult {
+        if snapshot.entries.is_empty() {
+            return Ok(());
+        }
 
-        let current_state = self.history.last().map_or(0, |r| r.state);
-        let new_state = current_state;
+        self.stack.clear();
 
-        let result = match result {
-            Ok(match_) => match match_.value {
-                RouteKind::Route(handler) => {
-                    self.history_kind.replace(&path_with_query, new_state)?;
-                    let mut leaving_route = self.history.pop();
-
-                    // Fire on_hiding on the leaving route
-                    if let Some(ref mut leaving) = leaving_route {
-                        if let Ok(route) = &mut leaving.route {
-                            route.on_hiding();
-                        }
+        for entry in &snapshot.entries {
+            if !self.matches(&entry.path) {
+                break;
+            }
+
+            match self.check_guards(state, &entry.path, NavigationKind::Initial) {
+                GuardResult::Allow => {}
+                GuardResult::Cancel => break,
+                GuardResult::Redirect(redirect) => {
+                    if self.stack.routes.is_empty() {
+                        self.history_kind.replace(&redirect, entry.state)?;
+                        self.navigate_impl(
+                            state,
+                            &redirect,
+                            TransitionConfig::none(),
+                            entry.state,
+                            1,
+                        )?;
+                    } else {
+                        self.navigate(state, redirect)?;
                     }
+                    break;
+                }
+            }
 
-                    let route = handler(Request {
-                        state,
-                        params: match_.params,
-                        query,
-                    });
-                    self.history.push(RouteState {
-                        path_with_query: path_with_query.clone(),
-                        route,
-                        id: ID.fetch_add(1, Ordering::SeqCst),
-                        state: new_state,
-                    });
+            if self.stack.routes.is_empty() {
+                self.history_kind.replace(&entry.path, entry.state)?;
+            } else {
+                self.history_kind.push(&entry.path, entry.state)?;
+            }
+            self.navigate_impl(state, &entry.path, TransitionConfig::none(), entry.state, 0)?;
+        }
+        self.emit_navigated(NavigationKind::Initial, None);
 
-                    // Fire on_showing on the newly created route
-                    if let Some(last) = self.history.last_mut() {
-                        if let Ok(route) = &mut last.route {
-                            route.on_showing();
-                        }
-                    }
+        Ok(())
+    }
 
-                    self.current_transition = Some(CurrentTransition {
-                        active_transition: ActiveTransition::forward(transition_config.clone())
-                            .with_default_duration(self.default_duration),
-                        leaving_route,
-                    });
+    /// Replace the current route with a custom transition.
+    /// Returns the same errors as [`EguiRouter::navigate_transition`].
+    pub fn replace_transition(
+        &mut self,
+        state: &mut State,
+        path: impl Into<String>,
+        transition_config: TransitionConfig,
+    ) -> RouterResult {
+        self.replace_with_redirects(state, &path.into(), transition_config, 0)
+    }
 
-                    Ok(())
-                }
-                RouteKind::Redirect(r) => {
-                    redirect = Some(r.clone());
-                    Ok(())
-                }
-            },
-            Err(MatchError::NotFound) => Err(RouterError::NotFound),
-        };
+    fn replace_with_redirects(
+        &mut self,
+        state: &mut State,
+        path_with_query: &str,
+        transition_config: TransitionConfig,
+        redirects: usize,
+    ) -> RouterResult {
+        match self.check_guards(state, path_with_query, NavigationKind::Replace) {
+            GuardResult::Allow => {}
+            GuardResult::Cancel => return Err(RouterError::GuardRejected),
+            GuardResult::Redirect(redirect) => {
+                self.check_redirects(&redirect, redirects)?;
+                return self.replace_with_redirects(
+                    state,
+                    &redirect,
+                    transition_config,
+                    redirects + 1,
+                );
+            }
+        }
 
-        if let Some(redirect) = redirect {
-            self.history_kind.replace(&redirect, new_state)?;
-            self.replace_transition(state, redirect, transition_config)?;
+        let from = self.active_route().map(ToOwned::to_owned);
+        let new_state = self.active_state();
+
+        match self.resolve(state, path_with_query, new_state)? {
+            Resolved::Route(route) => {
+                self.history_kind.replace(path_with_query, new_state)?;
+                let transition_config = route
+                    .modal
+                    .as_ref()
+                    .map_or(transition_config, |modal| modal.transition.clone());
+                let transition = ActiveTransition::forward(transition_config)
+                    .with_default_duration(self.default_duration);
+                self.stack.replace(route, transition);
+            }
+            Resolved::Child(route) => {
+                self.history_kind.replace(path_with_query, new_state)?;
+                if let Some(top) = self.stack.routes.last_mut() {
+                    top.path_with_query = path_with_query.to_string();
+                    if let Some(outlet) = &top.outlet {
+                        let transition = ActiveTransition::forward(
+                            self.nested[outlet.nested_id()].replace_transition.clone(),
+                        )
+                        .with_default_duration(self.default_duration);
+                        outlet.with_stack(|stack| stack.replace(route, transition));
+                    }
+                }
+            }
+            Resolved::Redirect(redirect) => {
+                self.check_redirects(&redirect, redirects)?;
+                self.history_kind.replace(&redirect, new_state)?;
+                return self.replace_with_redirects(
+                    state,
+                    &redirect,
+                    transition_config,
+                    redirects + 1,
+                );
+            }
         }
+        self.emit_navigated(NavigationKind::Replace, from);
 
-        result
+        Ok(())
     }
 
-    /// Replace the current route with the default transition
+    /// Replace the current route with the default transition, see
+    /// [`EguiRouter::replace_transition`]
     pub fn replace(&mut self, state: &mut State, path: impl Into<String>) -> RouterResult {
         self.replace_transition(state, path, self.replace_transition.clone())
     }
@@ -321,145 +943,212 @@ impl<State: 'static, H: History + Default> EguiRouter<State, H> {
     /// router's history from the browser's and break back/forward navigation.
     pub fn replace_url(&mut self, path: impl Into<String>) -> RouterResult {
         let path_with_query = path.into();
-        let current_state = self.history.last().map_or(0, |r| r.state);
+        let current_state = self.active_state();
         self.history_kind.replace(&path_with_query, current_state)?;
-        if let Some(last) = self.history.last_mut() {
+        if let Some(last) = self.stack.routes.last_mut() {
+            if let Some(outlet) = &last.outlet {
+                outlet.with_active_child(|child| {
+                    child.path_with_query.clone_from(&path_with_query);
+                });
+            }
             last.path_with_query = path_with_query;
         }
         Ok(())
     }
 
+    /// Find the history entry for a [`crate::history::HistoryEvent`].
+    /// Returns the index of the top level route and, for nested routes, the index of the child.
+    ///
+    /// Prefers an exact path-and-state match. If the path differs (a
+    /// route mirrored UI state into query params via `replace_url`
+    /// after pushing) fall back to matching on the state index alone
+    /// so we still recognise this as a back/forward navigation.
+    fn find_history_entry(&self, path: &str, state_index: u32) -> Option<(usize, Option<usize>)> {
+        let mut entries = Vec::new();
+        for (idx, route) in self.stack.routes.iter().enumerate() {
+            if let Some(outlet) = &route.outlet {
+                outlet.with_stack(|stack| {
+                    for (child_idx, child) in stack.routes.iter().enumerate() {
+                        entries.push((
+                            idx,
+                            Some(child_idx),
+                            child.path_with_query == path,
+                            child.state,
+                        ));
+                    }
+                });
+            } else {
+                entries.push((idx, None, route.path_with_query == path, route.state));
+            }
+        }
+
+        entries
+            .iter()
+            .find(|(_, _, same_path, state)| *same_path && *state == state_index)
+            .or_else(|| {
+                entries
+                    .iter()
+                    .find(|(_, _, _, state)| *state == state_index)
+            })
+            .map(|(idx, child_idx, _, _)| (*idx, *child_idx))
+    }
+
+    /// Handle a back navigation to an existing history entry that was triggered by the history
+    fn history_back_to(
+        &mut self,
+        path: &str,
+        route_state: u32,
+        idx: usize,
+        child_idx: Option<usize>,
+    ) {
+        let active_state = self.active_state();
+        let is_active_route = idx + 1 == self.stack.routes.len();
+
+        if let (Some(outlet), Some(child_idx)) = (&self.stack.routes[idx].outlet, child_idx) {
+            outlet.with_stack(|stack| {
+                stack.routes[child_idx].path_with_query = path.to_string();
+                // Keep the active child (if this is the active route) so that we can animate it out
+                stack.retain(|r| {
+                    r.state <= route_state || (is_active_route && r.state == active_state)
+                });
+            });
+        }
+        self.stack.routes[idx].path_with_query = path.to_string();
+
+        if is_active_route {
+            if route_state < active_state {
+                self.back_impl(self.backward_transition.clone());
+            }
+        } else {
+            let transition_config = self.modal_transition(self.backward_transition.clone());
+            let top_state = self.stack.routes[idx].state;
+            let active_top_state = self.stack.routes.last().map_or(0, |r| r.state);
+
+            // Retain all routes with a state less than or equal to the new state and the active state so that we can animate them out
+            self.stack
+                .retain(|r| r.state <= top_state || r.state == active_top_state);
+
+            let transition = ActiveTransition::backward(transition_config)
+                .with_default_duration(self.default_duration);
+            self.stack.back(transition);
+        }
+    }
+
     /// Render the router
-    #[allow(clippy::too_many_lines)]
     pub fn ui(&mut self, ui: &mut Ui, state: &mut State) {
         // Handle iOS-style swipe-to-go-back gesture
         // The active route can override the router's default via enable_swipe()
+        // Swiping back inside an outlet or from a modal is not supported
         let swipe_enabled = self
-            .history
+            .stack
+            .routes
             .last()
-            .and_then(|r| r.route.as_ref().ok())
-            .and_then(|route| route.enable_swipe())
+            .and_then(RouteState::enable_swipe)
             .unwrap_or(self.swipe_back_gesture_enabled);
-        if swipe_enabled && self.history.len() > 1 {
+        let outlet_has_history = self
+            .active_outlet()
+            .is_some_and(|outlet| outlet.history_len() > 1);
+        let is_modal = self.stack.routes.last().is_some_and(|r| r.modal.is_some());
+        if swipe_enabled && self.stack.routes.len() > 1 && !outlet_has_history && !is_modal {
             self.handle_swipe_gesture(ui, state);
         }
 
         for e in self.history_kind.update(ui.ctx()) {
+            self.events.emit(|| RouterEvent::HistorySync {
+                path: e.location.clone(),
+                state: e.state,
+            });
             let state_index = e.state.unwrap_or(0);
             let path = e.location;
-
-            // Prefer an exact path-and-state match. If the path differs (a
-            // route mirrored UI state into query params via `replace_url`
-            // after pushing) fall back to matching on the state index alone
-            // so we still recognise this as a back/forward navigation.
-            let matched = self
-                .history
-                .iter()
-                .position(|r| r.path_with_query == path && r.state == state_index)
-                .or_else(|| self.history.iter().position(|r| r.state == state_index));
-
-            if let Some(idx) = matched {
-                self.history[idx].path_with_query.clone_from(&path);
-                let route_state = self.history[idx].state;
-                let active_state = self.history.last().map_or(0, |r| r.state);
-
-                // Retain all routes with a state less than or equal to the new state and the active state so that we can animate them out
-                self.history
-                    .retain(|r| r.state <= route_state || r.state == active_state);
-
-                if route_state < active_state {
-                    self.back_impl(self.backward_transition.clone());
+            let from = self.active_route().map(ToOwned::to_owned);
+
+            // The history went forward again after a redirected back navigation, so the redirect
+            // replaces the right entry now
+            if self
+                .pending_redirect
+                .as_ref()
+                .is_some_and(|(pending_state, _)| *pending_state == state_index)
+            {
+                if let Some((_, redirect)) = self.pending_redirect.take() {
+                    let transition_config = self.replace_transition.clone();
+                    if let Err(error) =
+                        self.replace_with_redirects(state, &redirect, transition_config, 1)
+                    {
+                        self.emit_navigation_failed(&redirect, &error);
+                    }
                 }
-            } else {
-                self.navigate_impl(state, &path, self.forward_transition.clone(), state_index)
-                    .ok();
+                continue;
             }
-        }
 
-        if let Some((last, previous)) = self.history.split_last_mut() {
-            let result = if let Some(transition) = &mut self.current_transition {
-                let leaving_route_state = transition.leaving_route.as_mut().or(previous.last_mut());
-                Some(transition.active_transition.show(
-                    ui,
-                    state,
-                    (last.id, |ui, state| match &mut last.route {
-                        Ok(route) => {
-                            route.ui(ui, state);
+            if let Some((idx, child_idx)) = self.find_history_entry(&path, state_index) {
+                let is_back = state_index < self.active_state();
+                if is_back {
+                    match self.check_guards(state, &path, NavigationKind::Back) {
+                        GuardResult::Allow => {}
+                        GuardResult::Cancel => {
+                            // Undo the history navigation that already happened
+                            self.history_kind.forward().ok();
+                            continue;
                         }
-                        Err(err) => {
-                            (self.error_ui)(ui, state, err);
+                        GuardResult::Redirect(redirect) => {
+                            // Undo the history navigation first and replace the current route
+                            // once the history reports it (going forward is async in the
+                            // browser), so the redirect doesn't replace the previous entry
+                            self.pending_redirect = Some((self.active_state(), redirect));
+                            self.history_kind.forward().ok();
+                            ui.ctx().request_repaint();
+                            continue;
                         }
-                    }),
-                    leaving_route_state.map(|r| {
-                        (r.id, |ui: &mut Ui, state: &mut _| match &mut r.route {
-                            Ok(route) => {
-                                route.ui(ui, state);
-                            }
-                            Err(err) => {
-                                (self.error_ui)(ui, state, err);
-                            }
-                        })
-                    }),
-                ))
+                    }
+                }
+
+                self.history_back_to(&path, state_index, idx, child_idx);
+                if is_back {
+                    self.emit_navigated(NavigationKind::Back, from);
+                }
             } else {
-                ActiveTransition::show_default(ui, last.id, |ui| match &mut last.route {
-                    Ok(route) => {
-                        route.ui(ui, state);
+                match self.check_guards(state, &path, NavigationKind::Forward) {
+                    GuardResult::Allow => {
+                        match self.navigate_impl(
+                            state,
+                            &path,
+                            self.forward_transition.clone(),
+                            state_index,
+                            0,
+                        ) {
+                            Ok(()) => self.emit_navigated(NavigationKind::Forward, from),
+                            Err(error) => self.emit_navigation_failed(&path, &error),
+                        }
                     }
-                    Err(err) => {
-                        (self.error_ui)(ui, state, err);
+                    GuardResult::Cancel => {
+                        // Undo the history navigation that already happened
+                        self.history_kind.back().ok();
                     }
-                });
-                None
-            };
-
-            match result {
-                Some(ActiveTransitionResult::Done) => {
-                    if let Some(mut transition) = self.current_transition.take() {
-                        let is_backward = transition.active_transition.is_backward();
-                        if is_backward {
-                            // Leaving route is fully hidden
-                            if let Some(ref mut leaving) = transition.leaving_route {
-                                if let Ok(route) = &mut leaving.route {
-                                    route.on_hide();
-                                }
-                            }
-                            // Current top is fully shown again
-                            if let Some(last) = self.history.last_mut() {
-                                if let Ok(route) = &mut last.route {
-                                    route.on_shown();
-                                }
-                            }
-                        } else {
-                            // Forward/replace completed
-                            if let Some(ref mut leaving) = transition.leaving_route {
-                                // Replace: leaving route is fully hidden
-                                if let Ok(route) = &mut leaving.route {
-                                    route.on_hide();
-                                }
-                            } else if self.history.len() >= 2 {
-                                // Forward: previous top is now fully hidden
-                                let idx = self.history.len() - 2;
-                                if let Ok(route) = &mut self.history[idx].route {
-                                    route.on_hide();
-                                }
-                            }
-                            // The new top route is now fully shown
-                            if let Some(last) = self.history.last_mut() {
-                                if let Ok(route) = &mut last.route {
-                                    route.on_shown();
-                                }
-                            }
+                    GuardResult::Redirect(redirect) => {
+                        self.history_kind.replace(&redirect, state_index).ok();
+                        match self.navigate_impl(
+                            state,
+                            &redirect,
+                            self.forward_transition.clone(),
+                            state_index,
+                            1,
+                        ) {
+                            Ok(()) => self.emit_navigated(NavigationKind::Forward, from),
+                            Err(error) => self.emit_navigation_failed(&redirect, &error),
                         }
                     }
                 }
-                Some(ActiveTransitionResult::Continue) | None => {}
             }
         }
+
+        let modal_dismissed = self.stack.show(ui, state, &self.error_ui);
+        if modal_dismissed {
+            self.back(state).ok();
+        }
     }
 
     #[allow(clippy::too_many_lines)]
-    fn handle_swipe_gesture(&mut self, ui: &mut Ui, _state: &mut State) {
+    fn handle_swipe_gesture(&mut self, ui: &mut Ui, state: &mut State) {
         let gesture_id = Id::new("router_swipe_back_gesture");
 
         // Get or create gesture state
@@ -510,7 +1199,7 @@ impl<State: 'static, H: History + Default> EguiRouter<State, H> {
                                         SwipeBackGestureState::Swiping { distance: 0.0 };
 
                                     // Start a manual backward transition
-                                    if self.current_transition.is_none() {
+                                    if self.stack.current_transition.is_none() {
                                         let mut transition = CurrentTransition {
                                             active_transition: ActiveTransition::manual(
                                                 self.backward_transition.clone(),
@@ -520,7 +1209,7 @@ impl<State: 'static, H: History + Default> EguiRouter<State, H> {
                                         };
                                         // Initialize progress to 1.0 (fully showing current page)
                                         transition.active_transition.set_progress(1.0);
-                                        self.current_transition = Some(transition);
+                                        self.stack.set_transition(transition);
                                     }
                                 }
                             }
@@ -533,7 +1222,7 @@ impl<State: 'static, H: History + Default> EguiRouter<State, H> {
                             && velocity.y.abs() > 0.0
                         {
                             // Vertical movement dominates, cancel the gesture
-                            self.current_transition = None;
+                            self.stack.current_transition = None;
                             gesture_state = SwipeBackGestureState::Cancelled;
                         } else {
                             // Update the gesture distance (only positive horizontal movement)
@@ -549,7 +1238,7 @@ impl<State: 'static, H: History + Default> EguiRouter<State, H> {
                             }
 
                             // Update the transition progress
-                            if let Some(transition) = &mut self.current_transition {
+                            if let Some(transition) = &mut self.stack.current_transition {
                                 let screen_width = content_rect.width();
                                 let progress = 1.0 - (new_distance / screen_width).at_most(1.0);
                                 transition.active_transition.set_progress(progress);
@@ -574,36 +1263,24 @@ impl<State: 'static, H: History + Default> EguiRouter<State, H> {
                     let should_navigate_back = progress >= self.swipe_back_threshold
                         || velocity.x >= FLICK_VELOCITY_THRESHOLD;
 
-                    if should_navigate_back {
-                        let mut popped = self.history.pop();
-
-                        // Fire on_hiding on the popped route
-                        if let Some(ref mut leaving) = popped {
-                            if let Ok(route) = &mut leaving.route {
-                                route.on_hiding();
-                            }
-                        }
-
-                        // Fire on_showing on the route being revealed
-                        if let Some(last) = self.history.last_mut() {
-                            if let Ok(route) = &mut last.route {
-                                route.on_showing();
+                    // Stop the manual transition, the back navigation starts its own
+                    let progress = self
+                        .stack
+                        .current_transition
+                        .take()
+                        .map_or(1.0, |t| t.active_transition.progress());
+
+                    // Go back like [`EguiRouter::back`] does, so the guards run and the leaving
+                    // route is kept alive. If a guard cancelled the navigation, the current page
+                    // is shown again.
+                    if should_navigate_back && self.back(state).is_ok() {
+                        // Continue the transition from where the gesture left off (a guard
+                        // redirect replaces the route with a forward transition instead)
+                        if let Some(transition) = &mut self.stack.current_transition {
+                            if transition.active_transition.is_backward() {
+                                transition.active_transition.set_progress(1.0 - progress);
                             }
                         }
-
-                        // Complete the back navigation
-                        if let Some(transition) = &mut self.current_transition {
-                            let progress = transition.active_transition.progress();
-                            transition.active_transition =
-                                ActiveTransition::backward(self.backward_transition.clone());
-                            transition.active_transition.set_progress(1.0 - progress);
-                            transition.leaving_route = popped;
-                        }
-                        // Actually perform the back navigation
-                        self.history_kind.back().ok();
-                    } else {
-                        // Cancel the gesture - animate back to the current page
-                        self.current_transition = None;
                     }
 
                     gesture_state = SwipeBackGestureState::Idle;
@@ -614,7 +1291,7 @@ impl<State: 'static, H: History + Default> EguiRouter<State, H> {
         } else {
             // Pointer left the area, cancel the gesture
             if matches!(gesture_state, SwipeBackGestureState::Swiping { .. }) {
-                self.current_transition = None;
+                self.stack.current_transition = None;
             }
             gesture_state = SwipeBackGestureState::Idle;
         }
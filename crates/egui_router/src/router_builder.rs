@@ -1,7 +1,13 @@
-use crate::handler::MakeHandler;
+use crate::event::Events;
+use crate::guard::{Guard, GuardRequest, GuardResult};
+use crate::handler::{Handler, MakeHandler};
 use crate::history::History;
-use crate::route_kind::RouteKind;
-use crate::{EguiRouter, TransitionConfig};
+use crate::route_kind::{NestedRoutes, RouteKind, NESTED_PARAM};
+use crate::typed::TypedRoute;
+use crate::{
+    EguiRouter, ModalConfig, Outlet, Request, Route, RouterEvent, RouterSnapshot, TransitionConfig,
+};
+use std::rc::Rc;
 use std::sync::Arc;
 
 pub(crate) type ErrorUi<State> =
@@ -11,19 +17,31 @@ pub(crate) type LoadingUi<State> = Arc<Box<dyn Fn(&mut egui::Ui, &State) + Send
 /// Builder to create a [`EguiRouter`]
 pub struct RouterBuilder<State, H> {
     pub(crate) router: matchit::Router<RouteKind<State>>,
+    pub(crate) nested: Vec<NestedRoutes<State>>,
+    pub(crate) fallback: Option<Handler<State>>,
     pub(crate) default_route: Option<String>,
+    pub(crate) snapshot: Option<RouterSnapshot>,
+
+    pub(crate) guards: Vec<Guard<State>>,
+    pub(crate) route_guards: matchit::Router<Vec<Guard<State>>>,
 
     pub(crate) forward_transition: TransitionConfig,
     pub(crate) backward_transition: TransitionConfig,
     pub(crate) replace_transition: TransitionConfig,
 
     pub(crate) default_duration: Option<f32>,
+    pub(crate) max_redirects: usize,
 
     pub(crate) history_kind: Option<H>,
 
     pub(crate) error_ui: ErrorUi<State>,
     pub(crate) loading_ui: LoadingUi<State>,
 
+    pub(crate) keep_alive_limit: usize,
+    pub(crate) loader_timeout: f32,
+
+    pub(crate) events: Events,
+
     pub(crate) swipe_back_gesture_enabled: bool,
     pub(crate) swipe_back_edge_width: f32,
     pub(crate) swipe_back_threshold: f32,
@@ -40,11 +58,17 @@ impl<State: 'static, H: History + Default> RouterBuilder<State, H> {
     pub fn new() -> Self {
         Self {
             router: matchit::Router::new(),
+            nested: Vec::new(),
+            fallback: None,
             default_route: None,
+            snapshot: None,
+            guards: Vec::new(),
+            route_guards: matchit::Router::new(),
             forward_transition: TransitionConfig::default(),
             backward_transition: TransitionConfig::default(),
             replace_transition: TransitionConfig::fade(),
             default_duration: None,
+            max_redirects: 16,
             history_kind: None,
             error_ui: Arc::new(Box::new(|ui, _, err| {
                 ui.label(format!("Error: {err}"));
@@ -52,6 +76,9 @@ impl<State: 'static, H: History + Default> RouterBuilder<State, H> {
             loading_ui: Arc::new(Box::new(|ui, _| {
                 ui.spinner();
             })),
+            keep_alive_limit: 8,
+            loader_timeout: 0.5,
+            events: Events::default(),
             swipe_back_gesture_enabled: false,
             swipe_back_edge_width: 40.0,
             swipe_back_threshold: 0.4,
@@ -95,6 +122,14 @@ impl<State: 'static, H: History + Default> RouterBuilder<State, H> {
         self
     }
 
+    /// Restore a [`RouterSnapshot`] when building the router, see [`EguiRouter::restore`].
+    /// If the snapshot is empty or none of its entries match a route,
+    /// the router starts at the history's active route or the default path instead.
+    pub fn restore(mut self, snapshot: RouterSnapshot) -> Self {
+        self.snapshot = Some(snapshot);
+        self
+    }
+
     /// Set the history implementation
     pub fn history(mut self, history: H) -> Self {
         self.history_kind = Some(history);
@@ -157,6 +192,53 @@ impl<State: 'static, H: History + Default> RouterBuilder<State, H> {
         self
     }
 
+    /// Add a modal route. It's shown on top of the route below it (the route that was active
+    /// when navigating to the modal), which stays rendered. Going back or, depending on the
+    /// [`ModalConfig`], clicking outside of the modal dismisses it with the modal's transition.
+    /// The handler works like the one passed to [`RouterBuilder::route`].
+    ///
+    /// If the modal is the initial route (e.g. opened with a deep link), its parent route is
+    /// shown below it, see [`ModalConfig::parent`].
+    ///
+    /// Modal routes are only shown as modals at the top level, as children of a
+    /// [`RouterBuilder::nested`] route they behave like normal routes.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use egui::Ui;
+    /// # use egui_router::{EguiRouter, ModalConfig};
+    /// let router: EguiRouter<()> = EguiRouter::builder()
+    ///     .route("/photos/{id}", || |ui: &mut Ui, _: &mut ()| {
+    ///         ui.label("Photo");
+    ///     })
+    ///     // A bottom sheet, clicking above it dismisses it
+    ///     .modal_route(
+    ///         "/photos/{id}/share",
+    ///         ModalConfig::new().align(Some(egui::Align2::CENTER_BOTTOM)),
+    ///         || |ui: &mut Ui, _: &mut ()| {
+    ///             egui::Frame::popup(ui.style()).show(ui, |ui| {
+    ///                 ui.label("Share this photo");
+    ///             });
+    ///         },
+    ///     )
+    ///     .default_path("/photos/42")
+    ///     .build(&mut ());
+    /// ```
+    pub fn modal_route<HandlerArgs, Han: MakeHandler<State, HandlerArgs> + 'static>(
+        mut self,
+        route: &str,
+        config: ModalConfig,
+        mut handler: Han,
+    ) -> Self {
+        self.router
+            .insert(
+                route,
+                RouteKind::Modal(Box::new(move |req| handler.handle(req)), config),
+            )
+            .unwrap();
+        self
+    }
+
     /// Add an async route. Check the [matchit] documentation for information about the route syntax.
     /// The handler will be called with [`crate::OwnedRequest`] and should return a [Route].
     ///
@@ -185,10 +267,78 @@ impl<State: 'static, H: History + Default> RouterBuilder<State, H> {
     ///    .async_route("/:post", my_fallible_handler)
     ///    .build(&mut ());
     #[cfg(feature = "async")]
-    pub fn async_route<HandlerArgs, Han>(mut self, route: &str, handler: Han) -> Self
+    pub fn async_route<HandlerArgs, Han>(self, route: &str, handler: Han) -> Self
     where
         Han: crate::handler::AsyncMakeHandler<State, HandlerArgs> + 'static + Clone + Send + Sync,
         State: Clone + 'static + Send + Sync,
+    {
+        self.insert_async_route(route, false, move |owned| {
+            let handler = handler.clone();
+            async move { handler.handle(owned).await }
+        })
+    }
+
+    /// Add a route with a loader. Check the [matchit] documentation for information about the route syntax.
+    /// The loader is called with [`crate::OwnedRequest`] and loads the data for the route.
+    /// The router keeps showing the previous route until the data is loaded and then calls the
+    /// constructor with the data and starts the transition.
+    /// If loading takes longer than the [`RouterBuilder::loader_timeout`], the transition starts
+    /// anyways and the loading ui is shown until the data is loaded.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use egui::Ui;
+    /// # use egui_router::{EguiRouter, HandlerError, HandlerResult};
+    /// # #[cfg(feature = "async")]
+    /// async fn load_post(req: egui_router::OwnedRequest) -> HandlerResult<String> {
+    ///     let id = req.params.get("id").ok_or(HandlerError::NotFound)?;
+    ///     Ok(format!("Post {id}"))
+    /// }
+    ///
+    /// # #[cfg(feature = "async")]
+    /// let router: EguiRouter<()> = EguiRouter::builder()
+    ///     .loader_route("/post/{id}", load_post, |post: String| {
+    ///         move |ui: &mut Ui, _: &mut ()| {
+    ///             ui.label(&post);
+    ///         }
+    ///     })
+    ///     .build(&mut ());
+    /// ```
+    #[cfg(feature = "async")]
+    pub fn loader_route<T, Fut, R>(
+        self,
+        route: &str,
+        loader: impl Fn(crate::OwnedRequest<State>) -> Fut + 'static + Clone + Send + Sync,
+        constructor: impl Fn(T) -> R + 'static + Clone + Send + Sync,
+    ) -> Self
+    where
+        Fut: std::future::Future<Output = crate::HandlerResult<T>> + 'static + Send + Sync,
+        R: Route<State> + 'static + Send + Sync,
+        State: Clone + 'static + Send + Sync,
+    {
+        self.insert_async_route(route, true, move |owned| {
+            let loader = loader.clone();
+            let constructor = constructor.clone();
+            async move {
+                let data = loader(owned).await?;
+                Ok(Box::new(constructor(data)) as Box<dyn Route<State> + Send + Sync>)
+            }
+        })
+    }
+
+    #[cfg(feature = "async")]
+    fn insert_async_route<Fut>(
+        mut self,
+        route: &str,
+        wait_for_data: bool,
+        handler: impl Fn(crate::OwnedRequest<State>) -> Fut + 'static + Clone + Send + Sync,
+    ) -> Self
+    where
+        Fut: std::future::Future<Output = crate::HandlerResult<Box<dyn Route<State> + Send + Sync>>>
+            + 'static
+            + Send
+            + Sync,
+        State: Clone + 'static + Send + Sync,
     {
         let loading_ui = self.loading_ui.clone();
         let error_ui = self.error_ui.clone();
@@ -213,17 +363,14 @@ impl<State: 'static, H: History + Default> RouterBuilder<State, H> {
                         state: req.state.clone(),
                     };
 
-                    let handler = handler.clone();
-
                     let state_clone = req.state.clone();
                     let state_clone2 = req.state.clone();
 
                     let route = crate::async_route::AsyncRoute {
-                        suspense: egui_suspense::EguiSuspense::single_try_async(async move {
-                            handler.handle(owned).await
-                        })
-                        .loading_ui(move |ui| loading_ui(ui, &state_clone))
-                        .error_ui(move |ui, err, _| error_ui(ui, &state_clone2, err)),
+                        suspense: egui_suspense::EguiSuspense::single_try_async(handler(owned))
+                            .loading_ui(move |ui| loading_ui(ui, &state_clone))
+                            .error_ui(move |ui, err, _| error_ui(ui, &state_clone2, err)),
+                        wait_for_data,
                     };
 
                     Ok(Box::new(route))
@@ -233,6 +380,107 @@ impl<State: 'static, H: History + Default> RouterBuilder<State, H> {
         self
     }
 
+    /// Add a nested route. The `layout` handler is called with the [`Request`] and an [`Outlet`]
+    /// and should return a [Route] that renders the outlet via [`Outlet::ui`].
+    /// The child routes are added to the builder passed to `children`, their paths are relative
+    /// to `prefix` (use `/` for the path matching the `prefix` itself).
+    ///
+    /// Navigating between child routes of the same nested route keeps the layout route and only
+    /// animates inside the outlet, while the full path is still pushed to the [`History`].
+    /// The child builder inherits the transitions set so far, transitions set on the child builder
+    /// are used for navigations inside the outlet. Apart from that, only routes and redirects of
+    /// the child builder are used, and redirect targets are absolute paths.
+    /// Nested routes can't be nested further.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use egui::Ui;
+    /// # use egui_router::{EguiRouter, Outlet, Request};
+    /// let router: EguiRouter<()> = EguiRouter::builder()
+    ///     .nested(
+    ///         "/settings",
+    ///         |_req: Request, outlet: Outlet<()>| {
+    ///             move |ui: &mut Ui, state: &mut ()| {
+    ///                 ui.heading("Settings");
+    ///                 outlet.ui(ui, state);
+    ///             }
+    ///         },
+    ///         |children| {
+    ///             children
+    ///                 .route("/", || |ui: &mut Ui, _: &mut ()| {
+    ///                     ui.label("General");
+    ///                 })
+    ///                 .route("/profile", || |ui: &mut Ui, _: &mut ()| {
+    ///                     ui.label("Profile");
+    ///                 })
+    ///         },
+    ///     )
+    ///     .default_path("/settings")
+    ///     .build(&mut ());
+    /// ```
+    pub fn nested<R: Route<State> + 'static>(
+        mut self,
+        prefix: &str,
+        mut layout: impl FnMut(Request<State>, Outlet<State>) -> R + 'static,
+        children: impl FnOnce(Self) -> Self,
+    ) -> Self {
+        let mut child_builder = Self::new();
+        child_builder.error_ui = self.error_ui.clone();
+        child_builder.loading_ui = self.loading_ui.clone();
+        child_builder.forward_transition = self.forward_transition.clone();
+        child_builder.backward_transition = self.backward_transition.clone();
+        child_builder.replace_transition = self.replace_transition.clone();
+        let child_builder = children(child_builder);
+        assert!(
+            child_builder.nested.is_empty(),
+            "Nested routes can't be nested further"
+        );
+
+        let nested_id = self.nested.len();
+        self.nested.push(NestedRoutes {
+            layout: Box::new(move |req, outlet| Ok(Box::new(layout(req, outlet)))),
+            children: child_builder.router,
+            forward_transition: child_builder.forward_transition,
+            backward_transition: child_builder.backward_transition,
+            replace_transition: child_builder.replace_transition,
+        });
+
+        let prefix = prefix.trim_end_matches('/');
+        self.router
+            .insert(prefix, RouteKind::Nested(nested_id))
+            .unwrap();
+        self.router
+            .insert(
+                format!("{prefix}/{{*{NESTED_PARAM}}}"),
+                RouteKind::Nested(nested_id),
+            )
+            .unwrap();
+        self
+    }
+
+    /// Add a typed route. All patterns of the [`TypedRoute`] are registered, the handler is
+    /// called with the parsed route and the [`Request`].
+    /// Check [`TypedRoute`] for an example.
+    pub fn typed_route<T: TypedRoute + 'static, R: Route<State> + 'static>(
+        mut self,
+        handler: impl Fn(T, Request<State>) -> R + 'static,
+    ) -> Self {
+        let handler = Rc::new(handler);
+        for (index, pattern) in T::PATTERNS.iter().enumerate() {
+            let handler = handler.clone();
+            self.router
+                .insert(
+                    *pattern,
+                    RouteKind::Route(Box::new(move |req| {
+                        let route = T::from_request(index, &req)?;
+                        Ok(Box::new(handler(route, req)))
+                    })),
+                )
+                .unwrap();
+        }
+        self
+    }
+
     /// Add a redirect route. Whenever this route matches, it'll redirect to the route you specified.
     pub fn route_redirect(mut self, route: &str, redirect: impl Into<String>) -> Self {
         self.router
@@ -241,6 +489,87 @@ impl<State: 'static, H: History + Default> RouterBuilder<State, H> {
         self
     }
 
+    /// Set the fallback (404) route, shown for paths that don't match any route, instead of
+    /// returning [`crate::RouterError::NotFound`]. The handler works like the one passed to
+    /// [`RouterBuilder::route`], the request has no path params.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use egui::Ui;
+    /// # use egui_router::EguiRouter;
+    /// let mut router: EguiRouter<()> = EguiRouter::builder()
+    ///     .route("/", || |ui: &mut Ui, _: &mut ()| {
+    ///         ui.label("Home");
+    ///     })
+    ///     .fallback(|| |ui: &mut Ui, _: &mut ()| {
+    ///         ui.label("Page not found");
+    ///     })
+    ///     .default_path("/")
+    ///     .build(&mut ());
+    ///
+    /// router.navigate(&mut (), "/does-not-exist").unwrap();
+    /// assert_eq!(router.active_route(), Some("/does-not-exist"));
+    /// ```
+    pub fn fallback<HandlerArgs, Han: MakeHandler<State, HandlerArgs> + 'static>(
+        mut self,
+        mut handler: Han,
+    ) -> Self {
+        self.fallback = Some(Box::new(move |req| handler.handle(req)));
+        self
+    }
+
+    /// Set how many redirects (of redirect routes and guards) are followed for a single
+    /// navigation (default: 16). Exceeding it returns a [`crate::RouterError::RedirectLoop`],
+    /// so routes redirecting to each other don't overflow the stack.
+    pub fn max_redirects(mut self, max_redirects: usize) -> Self {
+        self.max_redirects = max_redirects;
+        self
+    }
+
+    /// Add a guard that runs before every navigation (including back navigations from the
+    /// history and the swipe-back gesture). The guard can allow, cancel or redirect the navigation.
+    /// Global guards run before route guards, the first guard that doesn't return
+    /// [`GuardResult::Allow`] decides.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use egui_router::{EguiRouter, GuardRequest, GuardResult};
+    /// struct AppState {
+    ///     logged_in: bool,
+    /// }
+    ///
+    /// let router: EguiRouter<AppState> = EguiRouter::builder()
+    ///     .guard(|req: GuardRequest<AppState>| {
+    ///         if req.path.starts_with("/settings") && !req.state.logged_in {
+    ///             GuardResult::Redirect("/login".to_string())
+    ///         } else {
+    ///             GuardResult::Allow
+    ///         }
+    ///     })
+    ///     .build(&mut AppState { logged_in: false });
+    /// ```
+    pub fn guard(
+        mut self,
+        guard: impl FnMut(GuardRequest<State>) -> GuardResult + 'static,
+    ) -> Self {
+        self.guards.push(Box::new(guard));
+        self
+    }
+
+    /// Add a guard that only runs for navigations to paths matching `route`.
+    /// The route uses the same syntax as [`RouterBuilder::route`], so e.g. `/settings/{*rest}`
+    /// guards a whole section.
+    pub fn route_guard(
+        mut self,
+        route: &str,
+        guard: impl FnMut(GuardRequest<State>) -> GuardResult + 'static,
+    ) -> Self {
+        let mut guards = self.route_guards.remove(route).unwrap_or_default();
+        guards.push(Box::new(guard));
+        self.route_guards.insert(route, guards).unwrap();
+        self
+    }
+
     /// Enable or disable the iOS-style swipe-to-go-back gesture (disabled by default)
     pub fn swipe_back_gesture(mut self, enabled: bool) -> Self {
         self.swipe_back_gesture_enabled = enabled;
@@ -253,6 +582,30 @@ impl<State: 'static, H: History + Default> RouterBuilder<State, H> {
         self
     }
 
+    /// Set how many removed routes are kept alive (default: 8). Only routes that return `true`
+    /// from [`Route::keep_alive`] are cached, the least recently removed ones are evicted first.
+    /// The router and the outlet of every nested route have their own cache. Set it to 0 to
+    /// disable keep-alive.
+    pub fn keep_alive_limit(mut self, limit: usize) -> Self {
+        self.keep_alive_limit = limit;
+        self
+    }
+
+    /// Set how long the router waits for a loading route (see [`Route::is_loading`] and
+    /// `loader_route` with the `async` feature) before starting the transition and showing the
+    /// route's loading ui instead (in seconds, default: 0.5).
+    pub fn loader_timeout(mut self, timeout: f32) -> Self {
+        self.loader_timeout = timeout;
+        self
+    }
+
+    /// Add a listener for the [`RouterEvent`]s, see [`EguiRouter::subscribe`].
+    /// Events emitted while building the router (like the initial navigation) are included.
+    pub fn on_event(self, listener: impl FnMut(&RouterEvent) + 'static) -> Self {
+        self.events.subscribe(listener);
+        self
+    }
+
     /// Set the threshold (as a fraction of screen width) for completing the back navigation (default: 0.4)
     pub fn swipe_back_threshold(mut self, threshold: f32) -> Self {
         self.swipe_back_threshold = threshold;
@@ -1,5 +1,7 @@
 use crate::TransitionConfig;
-use egui::{Id, Ui, UiBuilder, Vec2};
+use egui::{Id, InnerResponse, LayerId, Rect, Sense, Ui, UiBuilder, Vec2};
+use std::collections::HashMap;
+use std::hash::Hash;
 
 /// Trait for declaring a transition.
 /// Prefer [`ComposableTransitionTrait`] unless you need to create a new ui to apply the transition.
@@ -233,6 +235,7 @@ impl ActiveTransition {
         (in_id, content_in): (usize, impl FnOnce(&mut Ui, &mut State)),
         content_out: Option<(usize, impl FnOnce(&mut Ui, &mut State))>,
     ) -> ActiveTransitionResult {
+        let out_id = content_out.as_ref().map(|(id, _)| *id);
         if !self.manual_control {
             let dt = ui.input(|i| i.stable_dt);
             self.progress += dt / self.duration.unwrap_or_else(|| ui.style().animation_time);
@@ -254,33 +257,51 @@ impl ActiveTransition {
             (self.easing)(1.0 - t)
         };
 
+        // Shared elements move from the page we leave to the page we arrive at
+        let incoming_page = SharedElementPage {
+            id: in_id,
+            counterpart: out_id,
+            incoming: true,
+            t: eased_t,
+            offset: Vec2::ZERO,
+        };
+        let outgoing_page = out_id.map(|out_id| SharedElementPage {
+            id: out_id,
+            counterpart: Some(in_id),
+            incoming: false,
+            t: eased_t,
+            offset: Vec2::ZERO,
+        });
+
         if self.backward {
             with_temp_auto_id(ui, in_id, |ui| {
                 let mut out_ui =
                     self.out
                         .create_child_ui(ui, eased_t, Id::new("router_child").with(in_id));
-                content_in(&mut out_ui, state);
+                incoming_page.scope(ui, &mut out_ui, |ui| content_in(ui, state));
             });
 
-            if let Some((out_id, content_out)) = content_out {
+            if let (Some((out_id, content_out)), Some(outgoing_page)) = (content_out, outgoing_page)
+            {
                 with_temp_auto_id(ui, out_id, |ui| {
                     let mut in_ui = self.in_.create_child_ui(
                         ui,
                         eased_t_rev,
                         Id::new("router_child").with(out_id),
                     );
-                    content_out(&mut in_ui, state);
+                    outgoing_page.scope(ui, &mut in_ui, |ui| content_out(ui, state));
                 });
             }
         } else {
-            if let Some((out_id, content_out)) = content_out {
+            if let (Some((out_id, content_out)), Some(outgoing_page)) = (content_out, outgoing_page)
+            {
                 with_temp_auto_id(ui, out_id, |ui| {
                     let mut out_ui = self.out.create_child_ui(
                         ui,
                         eased_t_rev,
                         Id::new("router_child").with(out_id),
                     );
-                    content_out(&mut out_ui, state);
+                    outgoing_page.scope(ui, &mut out_ui, |ui| content_out(ui, state));
                 });
             }
 
@@ -288,7 +309,7 @@ impl ActiveTransition {
                 let mut in_ui =
                     self.in_
                         .create_child_ui(ui, eased_t, Id::new("router_child").with(in_id));
-                content_in(&mut in_ui, state);
+                incoming_page.scope(ui, &mut in_ui, |ui| content_in(ui, state));
             });
         }
 
@@ -301,16 +322,170 @@ impl ActiveTransition {
 
     pub fn show_default(ui: &mut Ui, with_id: usize, content: impl FnOnce(&mut Ui)) {
         with_temp_auto_id(ui, with_id, |ui| {
-            let mut ui = ui.new_child(
+            let mut child = ui.new_child(
                 UiBuilder::new()
                     .max_rect(ui.max_rect())
                     .id_salt(Id::new("router_child").with(with_id)),
             );
-            content(&mut ui);
+            let page = SharedElementPage {
+                id: with_id,
+                counterpart: None,
+                incoming: true,
+                t: 1.0,
+                offset: Vec2::ZERO,
+            };
+            page.scope(ui, &mut child, content);
+        });
+    }
+}
+
+/// The page (route) that is currently being rendered, used by [`shared_element`]
+#[derive(Debug, Clone, Copy)]
+struct SharedElementPage {
+    /// The id of the route
+    id: usize,
+    /// The id of the other route of the running transition
+    counterpart: Option<usize>,
+    /// Is this the page we arrive at?
+    incoming: bool,
+    /// The eased transition progress
+    t: f32,
+    /// How far the transition moved the page from its resting position
+    offset: Vec2,
+}
+
+impl SharedElementPage {
+    fn id() -> Id {
+        Id::new("router_shared_element_page")
+    }
+
+    /// Render the page with this as the current page
+    fn scope(mut self, parent: &Ui, page_ui: &mut Ui, content: impl FnOnce(&mut Ui)) {
+        self.offset = page_ui.max_rect().min - parent.max_rect().min;
+        let ctx = parent.ctx().clone();
+        let previous = ctx.data_mut(|data| {
+            let previous = data.get_temp::<Self>(Self::id());
+            data.insert_temp(Self::id(), self);
+            previous
+        });
+        content(page_ui);
+        ctx.data_mut(|data| match previous {
+            Some(previous) => data.insert_temp(Self::id(), previous),
+            None => data.remove::<Self>(Self::id()),
         });
     }
 }
 
+/// The resting rect of a shared element on a page
+#[derive(Debug, Clone, Copy)]
+struct SharedElementRect {
+    rect: Rect,
+    /// The pass the element was first shown in, since it was last hidden
+    first_pass: u64,
+    /// The last pass the element was shown in
+    last_pass: u64,
+}
+
+/// The resting rects of the shared elements, by element key and route id
+type SharedElementRects = HashMap<(Id, usize), SharedElementRect>;
+
+/// Show a shared element (also known as a hero element).
+///
+/// Tag the same element on two routes with the same `key`. While the router transitions between
+/// the routes, the element moves from its position on the route we leave to its position on the
+/// route we arrive at and is painted on top of both routes.
+/// This also works for back navigations and the swipe back gesture.
+///
+/// During the transition, `content` is shown in the moving rect, so e.g. an image that fills
+/// the available space will grow or shrink with it.
+///
+/// # Example
+/// ```rust
+/// # use egui::Ui;
+/// # fn thumbnail(ui: &mut Ui) {
+/// egui_router::transition::shared_element(ui, ("photo", 42), |ui| {
+///     ui.add_sized([64.0, 64.0], egui::Button::new("Photo 42"));
+/// });
+/// # }
+/// ```
+pub fn shared_element<R>(
+    ui: &mut Ui,
+    key: impl Hash,
+    content: impl FnOnce(&mut Ui) -> R,
+) -> InnerResponse<R> {
+    let key = Id::new(key);
+    let Some(page) = ui.data(|data| data.get_temp::<SharedElementPage>(SharedElementPage::id()))
+    else {
+        return ui.scope(content);
+    };
+
+    let rects_id = Id::new("router_shared_element_rects");
+    let pass = ui.ctx().cumulative_pass_nr();
+    let (own_rect, counterpart_rect) = ui.data_mut(|data| {
+        let rects = data.get_temp_mut_or_default::<SharedElementRects>(rects_id);
+        // Forget the elements of pages that weren't shown in the last pass, e.g. because the
+        // route was removed or is hidden below the active route
+        rects.retain(|_, rect| rect.last_pass + 1 >= pass);
+        // Only use the rects of elements that were already shown in a previous pass, so both
+        // pages agree on which one shows the element, no matter which one is rendered first
+        let shown_before = |page_id| {
+            rects
+                .get(&(key, page_id))
+                .filter(|rect| rect.first_pass < pass)
+                .map(|rect| rect.rect)
+        };
+        (
+            shown_before(page.id),
+            page.counterpart.and_then(shown_before),
+        )
+    });
+
+    let response = match (own_rect, counterpart_rect) {
+        (Some(own_rect), Some(counterpart_rect)) if page.incoming => {
+            // Keep the space on the page and show the element moving on top of both pages
+            let response = ui.allocate_exact_size(own_rect.size(), Sense::hover()).1;
+            let rect = Rect::from_min_max(
+                counterpart_rect.min.lerp(own_rect.min, page.t),
+                counterpart_rect.max.lerp(own_rect.max, page.t),
+            );
+            // Paint it on a layer right above the router's, clipped like the page
+            let layer_id = LayerId::new(ui.layer_id().order, key);
+            ui.ctx().set_sublayer(ui.layer_id(), layer_id);
+            let mut element_ui = ui.new_child(UiBuilder::new().layer_id(layer_id).max_rect(rect));
+            element_ui.set_clip_rect(ui.clip_rect());
+            element_ui.set_opacity(1.0);
+            InnerResponse::new(content(&mut element_ui), response)
+        }
+        (None, Some(_)) if page.incoming => {
+            // We don't know where the element rests on this page yet (e.g. in the first frame
+            // of the transition), so the page we leave keeps showing it for now
+            ui.scope_builder(UiBuilder::new().invisible(), content)
+        }
+        (Some(_), Some(_)) => {
+            // The incoming page shows the moving element
+            ui.scope_builder(UiBuilder::new().invisible(), content)
+        }
+        _ => ui.scope(content),
+    };
+
+    let resting_rect = response.response.rect.translate(-page.offset);
+    ui.data_mut(|data| {
+        data.get_temp_mut_or_default::<SharedElementRects>(rects_id)
+            .entry((key, page.id))
+            .and_modify(|rect| {
+                rect.rect = resting_rect;
+                rect.last_pass = pass;
+            })
+            .or_insert(SharedElementRect {
+                rect: resting_rect,
+                first_pass: pass,
+                last_pass: pass,
+            });
+    });
+
+    response
+}
+
 fn with_temp_auto_id(ui: &mut Ui, id: usize, content: impl FnOnce(&mut Ui)) {
     ui.skip_ahead_auto_ids(id);
     content(ui);
@@ -0,0 +1,128 @@
+use crate::{HandlerError, HandlerResult, Request};
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, CONTROLS};
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+/// A route that can be parsed from a [`Request`] and turned back into a path.
+///
+/// Implement it with `#[derive(TypedRoute)]` (requires the `derive` feature), register it with
+/// [`crate::RouterBuilder::typed_route`] and navigate to it with
+/// [`crate::EguiRouter::navigate`], since the derive also implements `From<T> for String`.
+///
+/// # Example
+/// ```rust
+/// # #[cfg(feature = "derive")]
+/// # {
+/// use egui::Ui;
+/// use egui_router::{EguiRouter, TypedRoute};
+///
+/// #[derive(TypedRoute)]
+/// enum AppRoute {
+///     #[route("/")]
+///     Home,
+///     #[route("/post/{id}")]
+///     Post {
+///         id: u32,
+///         #[route(query)]
+///         comment: Option<u32>,
+///     },
+/// }
+///
+/// let mut router: EguiRouter<()> = EguiRouter::builder()
+///     .typed_route(|route: AppRoute, _req| move |ui: &mut Ui, _: &mut ()| match &route {
+///         AppRoute::Home => {
+///             ui.label("Home");
+///         }
+///         AppRoute::Post { id, .. } => {
+///             ui.label(format!("Post {id}"));
+///         }
+///     })
+///     .default_path(AppRoute::Home)
+///     .build(&mut ());
+///
+/// router
+///     .navigate(&mut (), AppRoute::Post { id: 1, comment: Some(2) })
+///     .unwrap();
+/// assert_eq!(router.active_route(), Some("/post/1?comment=2"));
+/// # }
+/// ```
+pub trait TypedRoute: Sized {
+    /// The matchit route patterns, one per struct or enum variant
+    const PATTERNS: &'static [&'static str];
+
+    /// Parse the route from a request that matched `PATTERNS[pattern_index]`
+    fn from_request<State>(pattern_index: usize, request: &Request<State>) -> HandlerResult<Self>;
+
+    /// The path (including the query) of this route
+    fn to_path(&self) -> String;
+}
+
+/// The characters that are percent-encoded in a path segment
+/// (see <https://url.spec.whatwg.org/#path-percent-encode-set>, plus `/` and `%`)
+const PATH_SEGMENT: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'%')
+    .add(b'/')
+    .add(b'<')
+    .add(b'>')
+    .add(b'?')
+    .add(b'`')
+    .add(b'{')
+    .add(b'}');
+
+/// Parse a percent-encoded path param. Used by the [`TypedRoute`] derive.
+/// Returns [`HandlerError::NotFound`] if the param is missing or can't be parsed, since the
+/// path doesn't really match the route in that case.
+pub fn path_param<T: FromStr>(params: &matchit::Params, name: &str) -> HandlerResult<T> {
+    params
+        .get(name)
+        .and_then(|value| percent_decode_str(value).decode_utf8().ok())
+        .and_then(|value| value.parse().ok())
+        .ok_or(HandlerError::NotFound)
+}
+
+/// Append a percent-encoded path param to the path. Used by the [`TypedRoute`] derive.
+pub fn push_path_param(path: &mut String, value: &str) {
+    path.extend(utf8_percent_encode(value, PATH_SEGMENT));
+}
+
+/// Append a catch-all path param (`{*param}`) to the path, percent-encoding every segment but
+/// keeping the `/` between them. Used by the [`TypedRoute`] derive.
+pub fn push_catch_all_param(path: &mut String, value: &str) {
+    for (i, segment) in value.split('/').enumerate() {
+        if i > 0 {
+            path.push('/');
+        }
+        push_path_param(path, segment);
+    }
+}
+
+/// Parse an optional query param. Used by the [`TypedRoute`] derive.
+pub fn query_param<T: FromStr>(
+    query: &BTreeMap<Cow<str>, Cow<str>>,
+    name: &str,
+) -> HandlerResult<Option<T>> {
+    query
+        .get(name)
+        .map(|value| {
+            value
+                .parse()
+                .map_err(|_| HandlerError::Message(format!("Invalid query param `{name}`")))
+        })
+        .transpose()
+}
+
+/// Append the query to the path. Used by the [`TypedRoute`] derive.
+pub fn with_query(path: String, query: &[(&str, String)]) -> String {
+    if query.is_empty() {
+        path
+    } else {
+        let query = form_urlencoded::Serializer::new(String::new())
+            .extend_pairs(query)
+            .finish();
+        format!("{path}?{query}")
+    }
+}
@@ -0,0 +1,606 @@
+use egui::{Event, Modifiers, Order, PointerButton, Pos2, Sense, Ui, Vec2};
+use egui_kittest::Harness;
+use egui_router::history::{History, MemoryHistory};
+use egui_router::transition::shared_element;
+use egui_router::{
+    EguiRouter, GuardResult, ModalConfig, NavigationKind, Outlet, Request, Route, RouterBuilder,
+    RouterError, RouterEvent, TransitionConfig,
+};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+#[derive(Default)]
+struct AppState {
+    logged_in: bool,
+    /// The routes that were rendered in the last frame
+    rendered: Vec<&'static str>,
+    /// The shared elements shown in the last frame: the route, where and on which layer the
+    /// element was shown and whether it was visible
+    shared_elements: Vec<(&'static str, Pos2, Order, bool)>,
+}
+
+struct App {
+    router: EguiRouter<AppState, MemoryHistory>,
+    state: AppState,
+}
+
+/// A route that records that it was rendered
+fn page(name: &'static str) -> impl Route<AppState> {
+    move |_ui: &mut Ui, state: &mut AppState| state.rendered.push(name)
+}
+
+fn routes() -> RouterBuilder<AppState, MemoryHistory> {
+    EguiRouter::builder()
+        .history(MemoryHistory::new())
+        .default_path("/")
+        .route("/", || page("home"))
+        .route("/a", || page("a"))
+        .route("/b", || page("b"))
+        .route("/login", || page("login"))
+        .route("/settings", || page("settings"))
+        .route("/admin", || page("admin"))
+}
+
+fn harness(builder: RouterBuilder<AppState, MemoryHistory>) -> Harness<'static, App> {
+    let mut state = AppState::default();
+    let router = builder.build(&mut state);
+    Harness::builder().with_step_dt(0.1).build_ui_state(
+        |ui, app: &mut App| {
+            app.state.rendered.clear();
+            app.state.shared_elements.clear();
+            app.router.ui(ui, &mut app.state);
+        },
+        App { router, state },
+    )
+}
+
+fn navigate(harness: &mut Harness<'_, App>, path: &str) {
+    let App { router, state } = harness.state_mut();
+    router.navigate(state, path).unwrap();
+    harness.run();
+}
+
+fn back(harness: &mut Harness<'_, App>) {
+    let App { router, state } = harness.state_mut();
+    router.back(state).unwrap();
+    harness.run();
+}
+
+/// Click with the primary pointer button
+fn click(harness: &mut Harness<'_, App>, pos: Pos2) {
+    let input = harness.input_mut();
+    input.events.push(Event::PointerMoved(pos));
+    for pressed in [true, false] {
+        input.events.push(Event::PointerButton {
+            pos,
+            button: PointerButton::Primary,
+            pressed,
+            modifiers: Modifiers::NONE,
+        });
+    }
+    harness.run();
+}
+
+fn history(router: &EguiRouter<AppState, MemoryHistory>) -> Vec<&str> {
+    router.history().collect()
+}
+
+#[test]
+fn guards_redirect_and_cancel() {
+    let mut state = AppState::default();
+    let mut router = routes()
+        .guard(|req| {
+            if req.path.starts_with("/settings") && !req.state.logged_in {
+                GuardResult::Redirect("/login".to_string())
+            } else {
+                GuardResult::Allow
+            }
+        })
+        .route_guard("/admin", |_| GuardResult::Cancel)
+        .build(&mut state);
+
+    router.navigate(&mut state, "/settings").unwrap();
+    assert_eq!(router.active_route(), Some("/login"));
+    assert_eq!(history(&router), ["/", "/login"]);
+
+    let result = router.navigate(&mut state, "/admin");
+    assert!(matches!(result, Err(RouterError::GuardRejected)));
+    assert_eq!(history(&router), ["/", "/login"]);
+
+    state.logged_in = true;
+    router.navigate(&mut state, "/settings").unwrap();
+    assert_eq!(history(&router), ["/", "/login", "/settings"]);
+}
+
+#[test]
+fn back_guard_redirect_replaces() {
+    let mut state = AppState::default();
+    let mut router = routes()
+        .guard(|req| {
+            if req.kind == NavigationKind::Back && req.path == "/" && !req.state.logged_in {
+                GuardResult::Redirect("/login".to_string())
+            } else {
+                GuardResult::Allow
+            }
+        })
+        .build(&mut state);
+
+    router.navigate(&mut state, "/a").unwrap();
+    router.back(&mut state).unwrap();
+    assert_eq!(history(&router), ["/", "/login"]);
+
+    state.logged_in = true;
+    router.back(&mut state).unwrap();
+    assert_eq!(history(&router), ["/"]);
+}
+
+#[test]
+fn history_back_guard_redirect_replaces() {
+    let mut harness = harness(routes().guard(|req| {
+        if req.kind == NavigationKind::Back && req.path == "/" && !req.state.logged_in {
+            GuardResult::Redirect("/login".to_string())
+        } else {
+            GuardResult::Allow
+        }
+    }));
+    navigate(&mut harness, "/a");
+
+    // Like a click on the browser's back button
+    harness
+        .state_mut()
+        .router
+        .history_kind_mut()
+        .back()
+        .unwrap();
+    harness.run();
+
+    let App { router, state } = harness.state();
+    assert_eq!(router.active_route(), Some("/login"));
+    assert_eq!(history(router), ["/", "/login"]);
+    assert_eq!(state.rendered, ["login"]);
+    let memory = router.history_kind();
+    assert_eq!(memory.back_stack().collect::<Vec<_>>(), ["/"]);
+    assert_eq!(memory.active_route(), Some(("/login".to_string(), Some(1))));
+}
+
+#[test]
+fn redirect_loop() {
+    let mut state = AppState::default();
+    let mut router = routes()
+        .route_redirect("/loop", "/other-loop")
+        .route_redirect("/other-loop", "/loop")
+        .build(&mut state);
+    let result = router.navigate(&mut state, "/loop");
+    assert!(matches!(result, Err(RouterError::RedirectLoop(_))));
+    assert_eq!(history(&router), ["/"]);
+
+    // Guards count towards the limit, too
+    let redirects = Rc::new(Cell::new(0));
+    let mut router = routes()
+        .max_redirects(3)
+        .guard({
+            let redirects = redirects.clone();
+            move |req| match req.path {
+                "/a" => {
+                    redirects.set(redirects.get() + 1);
+                    GuardResult::Redirect("/b".to_string())
+                }
+                "/b" => GuardResult::Redirect("/a".to_string()),
+                _ => GuardResult::Allow,
+            }
+        })
+        .build(&mut state);
+    let result = router.navigate(&mut state, "/a");
+    assert!(matches!(result, Err(RouterError::RedirectLoop(_))));
+    assert_eq!(redirects.get(), 2);
+    assert_eq!(history(&router), ["/"]);
+}
+
+#[test]
+fn initial_navigation_falls_back() {
+    let events = Rc::new(RefCell::new(Vec::new()));
+    let builder = |initial: &str| {
+        let mut memory = MemoryHistory::new();
+        memory.push(initial, 0).unwrap();
+        let events = events.clone();
+        routes()
+            .history(memory)
+            .route_guard("/admin", |_| GuardResult::Cancel)
+            .route_redirect("/loop", "/loop")
+            .on_event(move |event| match event {
+                RouterEvent::NavigationFailed { path, .. } => {
+                    events.borrow_mut().push(format!("failed {path}"));
+                }
+                RouterEvent::Navigated { to, .. } => {
+                    events.borrow_mut().push(format!("navigated {to:?}"));
+                }
+                _ => {}
+            })
+    };
+    let mut state = AppState::default();
+
+    // A cancelled initial route falls back to the default path
+    let router = builder("/admin").build(&mut state);
+    assert_eq!(history(&router), ["/"]);
+    assert_eq!(
+        router.history_kind().active_route(),
+        Some(("/".to_string(), Some(0)))
+    );
+
+    // So does a redirect loop
+    let router = builder("/loop").build(&mut state);
+    assert_eq!(history(&router), ["/"]);
+
+    // If the default path fails, too, the fallback route is shown
+    let router = builder("/admin")
+        .guard(|_| GuardResult::Cancel)
+        .fallback(|| page("not found"))
+        .build(&mut state);
+    assert_eq!(history(&router), ["/admin"]);
+
+    // Without a fallback route, there is no route to navigate to
+    let router = builder("/admin")
+        .guard(|_| GuardResult::Cancel)
+        .build(&mut state);
+    assert_eq!(router.history_len(), 0);
+
+    assert_eq!(
+        *events.borrow(),
+        [
+            "failed /admin",
+            "navigated Some(\"/\")",
+            "failed /loop",
+            "navigated Some(\"/\")",
+            "failed /admin",
+            "failed /",
+            "navigated Some(\"/admin\")",
+            "failed /admin",
+            "failed /",
+        ]
+    );
+}
+
+#[test]
+fn memory_history_back_and_forward() {
+    let kinds = Rc::new(RefCell::new(Vec::new()));
+    let mut harness = harness(routes().on_event({
+        let kinds = kinds.clone();
+        move |event| {
+            if let RouterEvent::Navigated { kind, .. } = event {
+                kinds.borrow_mut().push(*kind);
+            }
+        }
+    }));
+
+    navigate(&mut harness, "/a");
+    navigate(&mut harness, "/b");
+    back(&mut harness);
+    assert_eq!(harness.state().router.active_route(), Some("/a"));
+    assert_eq!(harness.state().state.rendered, ["a"]);
+
+    // Going forward is picked up on the next frame
+    harness.state_mut().router.forward().unwrap();
+    assert_eq!(harness.state().router.active_route(), Some("/a"));
+    harness.run();
+    assert_eq!(harness.state().router.active_route(), Some("/b"));
+    assert_eq!(harness.state().state.rendered, ["b"]);
+    assert_eq!(history(&harness.state().router), ["/", "/a", "/b"]);
+
+    // There is nothing to go forward to anymore
+    harness.state_mut().router.forward().unwrap();
+    harness.run();
+    assert_eq!(harness.state().router.active_route(), Some("/b"));
+
+    assert_eq!(
+        *kinds.borrow(),
+        [
+            NavigationKind::Initial,
+            NavigationKind::Push,
+            NavigationKind::Push,
+            NavigationKind::Back,
+            NavigationKind::Forward,
+        ]
+    );
+}
+
+#[test]
+fn nested_routes() {
+    let layouts = Rc::new(Cell::new(0));
+    let mut harness = harness(routes().nested(
+        "/account",
+        {
+            let layouts = layouts.clone();
+            move |_req: Request<AppState>, outlet: Outlet<AppState>| {
+                layouts.set(layouts.get() + 1);
+                move |ui: &mut Ui, state: &mut AppState| {
+                    state.rendered.push("account");
+                    outlet.ui(ui, state);
+                }
+            }
+        },
+        |children| {
+            children
+                .route("/", || page("general"))
+                .route("/profile", || page("profile"))
+        },
+    ));
+
+    navigate(&mut harness, "/account");
+    assert_eq!(harness.state().state.rendered, ["account", "general"]);
+
+    // Navigating between child routes keeps the layout route
+    navigate(&mut harness, "/account/profile");
+    assert_eq!(harness.state().state.rendered, ["account", "profile"]);
+    assert_eq!(history(&harness.state().router), ["/", "/account/profile"]);
+    let snapshot = harness.state().router.snapshot();
+    let paths: Vec<_> = snapshot.entries.iter().map(|e| e.path.as_str()).collect();
+    assert_eq!(paths, ["/", "/account", "/account/profile"]);
+    assert_eq!(layouts.get(), 1);
+
+    // Going back goes back inside the outlet first
+    back(&mut harness);
+    assert_eq!(harness.state().router.active_route(), Some("/account"));
+    assert_eq!(harness.state().state.rendered, ["account", "general"]);
+    assert_eq!(layouts.get(), 1);
+
+    // ... and then to the parent
+    back(&mut harness);
+    assert_eq!(harness.state().router.active_route(), Some("/"));
+    assert_eq!(harness.state().state.rendered, ["home"]);
+}
+
+/// A route showing a shared element below `offset` points of space
+fn hero_page(name: &'static str, offset: f32) -> impl Route<AppState> {
+    move |ui: &mut Ui, state: &mut AppState| {
+        state.rendered.push(name);
+        ui.add_space(offset);
+        shared_element(ui, "hero", |ui| {
+            let (rect, _) = ui.allocate_exact_size(Vec2::splat(50.0), Sense::hover());
+            let element = (name, rect.min, ui.layer_id().order, ui.is_visible());
+            state.shared_elements.push(element);
+        });
+    }
+}
+
+#[test]
+fn shared_element_transition() {
+    let mut harness = harness(
+        routes()
+            .transition(
+                TransitionConfig::fade()
+                    .with_easing(|t| t)
+                    .with_duration(1.0),
+            )
+            .route("/list", || hero_page("list", 0.0))
+            .route("/detail", || hero_page("detail", 200.0)),
+    );
+    let visible = |harness: &Harness<'_, App>| {
+        let elements = &harness.state().state.shared_elements;
+        elements
+            .iter()
+            .filter(|(.., visible)| *visible)
+            .copied()
+            .collect::<Vec<_>>()
+    };
+    let App { router, state } = harness.state_mut();
+    router.navigate(state, "/list").unwrap();
+    harness.run_steps(12);
+    let [(_, start, order, _)] = visible(&harness)[..] else {
+        panic!("expected a single visible element");
+    };
+
+    // The incoming route doesn't know where the element rests yet, so it's shown on the
+    // route we leave
+    let App { router, state } = harness.state_mut();
+    router.navigate(state, "/detail").unwrap();
+    harness.step();
+    assert_eq!(visible(&harness), [("list", start, order, true)]);
+
+    // Then it moves from the rect on the route we leave to the one on the route we arrive at
+    harness.run_steps(4);
+    let [(name, pos, element_order, _)] = visible(&harness)[..] else {
+        panic!("expected a single visible element");
+    };
+    assert_eq!(name, "detail");
+    assert_eq!(element_order, order);
+    let end = start + Vec2::Y * 200.0;
+    let t = (pos.y - start.y) / (end.y - start.y);
+    assert!(t > 0.3 && t < 0.7, "expected the element halfway, got {t}");
+    assert!((pos.x - start.x).abs() < 0.1);
+
+    harness.run_steps(6);
+    assert_eq!(visible(&harness), [("detail", end, order, true)]);
+}
+
+struct KeptAlive {
+    name: &'static str,
+    evicted: Rc<RefCell<Vec<&'static str>>>,
+}
+
+impl Route<AppState> for KeptAlive {
+    fn ui(&mut self, _ui: &mut Ui, state: &mut AppState) {
+        state.rendered.push(self.name);
+    }
+
+    fn keep_alive(&self) -> bool {
+        true
+    }
+
+    fn on_evict(&mut self) {
+        self.evicted.borrow_mut().push(self.name);
+    }
+}
+
+#[test]
+fn keep_alive_eviction() {
+    let created = Rc::new(RefCell::new(Vec::new()));
+    let evicted = Rc::new(RefCell::new(Vec::new()));
+    let kept_alive = |name: &'static str| {
+        let created = created.clone();
+        let evicted = evicted.clone();
+        move || {
+            created.borrow_mut().push(name);
+            KeptAlive {
+                name,
+                evicted: evicted.clone(),
+            }
+        }
+    };
+    let mut harness = harness(
+        EguiRouter::builder()
+            .history(MemoryHistory::new())
+            .default_path("/")
+            .keep_alive_limit(1)
+            .route("/", || page("home"))
+            .route("/a", kept_alive("a"))
+            .route("/b", kept_alive("b")),
+    );
+
+    // The route is kept alive after going back and reused when navigating to it again
+    navigate(&mut harness, "/a");
+    back(&mut harness);
+    navigate(&mut harness, "/a");
+    assert_eq!(*created.borrow(), ["a"]);
+    assert_eq!(harness.state().state.rendered, ["a"]);
+
+    // Caching another route evicts it, since the limit is 1
+    back(&mut harness);
+    navigate(&mut harness, "/b");
+    back(&mut harness);
+    assert_eq!(*evicted.borrow(), ["a"]);
+
+    navigate(&mut harness, "/a");
+    assert_eq!(*created.borrow(), ["a", "b", "a"]);
+}
+
+#[test]
+fn modal_back() {
+    let mut harness = harness(routes().modal_route("/share", ModalConfig::new(), || page("share")));
+
+    // The route below the modal stays visible
+    navigate(&mut harness, "/share");
+    assert_eq!(harness.state().state.rendered, ["home", "share"]);
+
+    back(&mut harness);
+    assert_eq!(harness.state().router.active_route(), Some("/"));
+    assert_eq!(harness.state().state.rendered, ["home"]);
+
+    // Clicking outside of the modal dismisses it
+    navigate(&mut harness, "/share");
+    click(&mut harness, Pos2::new(10.0, 10.0));
+    assert_eq!(harness.state().router.active_route(), Some("/"));
+    assert_eq!(harness.state().state.rendered, ["home"]);
+    assert_eq!(history(&harness.state().router), ["/"]);
+}
+
+#[test]
+fn modal_deep_link() {
+    let deep_link = |path: &str| {
+        let mut memory = MemoryHistory::new();
+        memory.push(path, 0).unwrap();
+        routes()
+            .history(memory)
+            .route("/photos/{id}", || page("photo"))
+            .modal_route("/photos/{id}/share", ModalConfig::new(), || page("share"))
+            .modal_route("/photos/{id}/edit", ModalConfig::new().parent("/a"), || {
+                page("edit")
+            })
+    };
+
+    // The parent path is shown below the modal
+    let mut share = harness(deep_link("/photos/42/share"));
+    assert_eq!(share.state().state.rendered, ["photo", "share"]);
+    assert_eq!(
+        history(&share.state().router),
+        ["/photos/42", "/photos/42/share"]
+    );
+    click(&mut share, Pos2::new(10.0, 10.0));
+    assert_eq!(share.state().router.active_route(), Some("/photos/42"));
+    assert_eq!(share.state().state.rendered, ["photo"]);
+    let memory = share.state().router.history_kind();
+    assert_eq!(
+        memory.active_route(),
+        Some(("/photos/42".to_string(), Some(0)))
+    );
+
+    // ... or the one set with ModalConfig::parent
+    let mut edit = harness(deep_link("/photos/42/edit"));
+    assert_eq!(history(&edit.state().router), ["/a", "/photos/42/edit"]);
+    back(&mut edit);
+    assert_eq!(edit.state().state.rendered, ["a"]);
+}
+
+struct Loading {
+    name: &'static str,
+    loaded: Rc<Cell<bool>>,
+}
+
+impl Route<AppState> for Loading {
+    fn ui(&mut self, _ui: &mut Ui, state: &mut AppState) {
+        state.rendered.push(self.name);
+    }
+
+    fn is_loading(&mut self, _ctx: &egui::Context) -> bool {
+        !self.loaded.get()
+    }
+}
+
+#[test]
+fn loader_timeout() {
+    let loaded = Rc::new(Cell::new(false));
+    let loading = |name: &'static str, loaded: Rc<Cell<bool>>| {
+        move || Loading {
+            name,
+            loaded: loaded.clone(),
+        }
+    };
+    let mut harness = harness(
+        routes()
+            .loader_timeout(0.5)
+            .route("/slow", loading("slow", loaded.clone()))
+            .route("/never", loading("never", Rc::new(Cell::new(false)))),
+    );
+
+    // The previous route is shown until the route is done loading
+    let App { router, state } = harness.state_mut();
+    router.navigate(state, "/slow").unwrap();
+    harness.run_steps(3);
+    assert_eq!(harness.state().state.rendered, ["home"]);
+    loaded.set(true);
+    harness.run();
+    assert_eq!(harness.state().state.rendered, ["slow"]);
+
+    // ... or until the loader timeout is reached
+    let App { router, state } = harness.state_mut();
+    router.navigate(state, "/never").unwrap();
+    harness.run_steps(4);
+    assert_eq!(harness.state().state.rendered, ["slow"]);
+    harness.run_steps(2);
+    harness.run();
+    assert_eq!(harness.state().state.rendered, ["never"]);
+}
+
+#[test]
+fn snapshot_restore() {
+    let mut state = AppState::default();
+    let mut router = routes().build(&mut state);
+    router.navigate(&mut state, "/a").unwrap();
+    router.navigate(&mut state, "/b?tab=2").unwrap();
+
+    let snapshot = router.snapshot();
+    let paths: Vec<_> = snapshot.entries.iter().map(|e| e.path.as_str()).collect();
+    assert_eq!(paths, ["/", "/a", "/b?tab=2"]);
+
+    let mut restored = routes().restore(snapshot.clone()).build(&mut state);
+    assert_eq!(history(&restored), ["/", "/a", "/b?tab=2"]);
+    assert_eq!(restored.snapshot(), snapshot);
+    restored.back(&mut state).unwrap();
+    assert_eq!(restored.active_route(), Some("/a"));
+
+    // Restoring stops at the first entry a guard cancels
+    let mut router = routes()
+        .route_guard("/b", |_| GuardResult::Cancel)
+        .build(&mut state);
+    router.restore(&mut state, &snapshot).unwrap();
+    assert_eq!(history(&router), ["/", "/a"]);
+}
@@ -0,0 +1,73 @@
+use egui::Ui;
+use egui_router::{EguiRouter, Request, TypedRoute};
+
+#[derive(TypedRoute, Debug, Clone, PartialEq)]
+enum AppRoute {
+    #[route("/")]
+    Home,
+    #[route("/user/{name}")]
+    User {
+        name: String,
+        #[route(query)]
+        tab: Option<String>,
+    },
+    #[route("/files/{*path}")]
+    File { path: String },
+}
+
+/// Navigate to the route and return the route the handler was called with
+fn round_trip(route: &AppRoute) -> (String, AppRoute) {
+    let mut parsed = Vec::new();
+    let mut router: EguiRouter<Vec<AppRoute>> = EguiRouter::builder()
+        .typed_route(|route: AppRoute, req: Request<Vec<AppRoute>>| {
+            req.state.push(route);
+            |_: &mut Ui, _: &mut Vec<AppRoute>| {}
+        })
+        .default_path(AppRoute::Home)
+        .build(&mut parsed);
+    router.navigate(&mut parsed, route).unwrap();
+
+    (
+        router.active_route().unwrap().to_string(),
+        parsed.pop().unwrap(),
+    )
+}
+
+#[test]
+fn path_params_are_percent_encoded() {
+    let route = AppRoute::User {
+        name: "a/b c?d#e%f".to_string(),
+        tab: Some("x&y=z".to_string()),
+    };
+    let (path, parsed) = round_trip(&route);
+    assert_eq!(path, "/user/a%2Fb%20c%3Fd%23e%25f?tab=x%26y%3Dz");
+    assert_eq!(parsed, route);
+}
+
+#[test]
+fn unicode_path_params_round_trip() {
+    let route = AppRoute::User {
+        name: "Zoë 🦀".to_string(),
+        tab: None,
+    };
+    let (path, parsed) = round_trip(&route);
+    assert_eq!(path, "/user/Zo%C3%AB%20%F0%9F%A6%80");
+    assert_eq!(parsed, route);
+}
+
+#[test]
+fn catch_all_params_keep_slashes() {
+    let route = AppRoute::File {
+        path: "docs/my notes/todo?.md".to_string(),
+    };
+    let (path, parsed) = round_trip(&route);
+    assert_eq!(path, "/files/docs/my%20notes/todo%3F.md");
+    assert_eq!(parsed, route);
+}
+
+#[test]
+fn unit_variant() {
+    let (path, parsed) = round_trip(&AppRoute::Home);
+    assert_eq!(path, "/");
+    assert_eq!(parsed, AppRoute::Home);
+}
@@ -0,0 +1,396 @@
+#![doc = include_str!("../README.md")]
+#![forbid(unsafe_code)]
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::spanned::Spanned;
+use syn::{
+    parse_macro_input, Attribute, Data, DeriveInput, Fields, GenericArgument, LitStr,
+    PathArguments, Type,
+};
+
+/// Derive `egui_router::TypedRoute` for a struct or an enum.
+///
+/// Structs need a `#[route("/path/{param}")]` attribute, for enums every variant needs one.
+/// Fields that are used as path params must be named like the param, fields marked with
+/// `#[route(query)]` are read from the query. Query fields of type `Option<T>` are optional.
+/// Values are written with `ToString` and read with `FromStr`, path params are percent-encoded.
+#[proc_macro_derive(TypedRoute, attributes(route))]
+pub fn derive_typed_route(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// A part of a route pattern
+#[derive(Debug, PartialEq)]
+enum Segment {
+    Literal(String),
+    Param {
+        name: String,
+        /// Is this a catch-all param (`{*name}`)?
+        catch_all: bool,
+    },
+}
+
+/// Split a matchit route pattern into literals and params
+fn parse_pattern(pattern: &LitStr) -> syn::Result<Vec<Segment>> {
+    let value = pattern.value();
+    let mut segments = Vec::new();
+    let mut literal = String::new();
+    let mut chars = value.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literal.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literal.push('}');
+            }
+            '{' => {
+                let mut name = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => name.push(c),
+                        None => {
+                            return Err(syn::Error::new(pattern.span(), "Unclosed `{` in route"))
+                        }
+                    }
+                }
+                let catch_all = name.starts_with('*');
+                let name = name.trim_start_matches('*').to_string();
+                if name.is_empty() {
+                    return Err(syn::Error::new(pattern.span(), "Empty param name in route"));
+                }
+                if !literal.is_empty() {
+                    segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                }
+                segments.push(Segment::Param { name, catch_all });
+            }
+            c => literal.push(c),
+        }
+    }
+    if !literal.is_empty() {
+        segments.push(Segment::Literal(literal));
+    }
+    Ok(segments)
+}
+
+/// The `#[route("...")]` pattern of a struct or variant
+fn route_pattern(attrs: &[Attribute], span: proc_macro2::Span) -> syn::Result<LitStr> {
+    attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("route"))
+        .ok_or_else(|| syn::Error::new(span, "Missing `#[route(\"/path\")]` attribute"))?
+        .parse_args::<LitStr>()
+}
+
+/// Is the field marked with `#[route(query)]`?
+fn is_query_field(attrs: &[Attribute]) -> syn::Result<bool> {
+    let mut query = false;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("route")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("query") {
+                query = true;
+                Ok(())
+            } else {
+                Err(meta.error("Expected `#[route(query)]`"))
+            }
+        })?;
+    }
+    Ok(query)
+}
+
+/// The `T` of an `Option<T>`
+fn option_inner(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    match args.args.first()? {
+        GenericArgument::Type(inner) => Some(inner),
+        _ => None,
+    }
+}
+
+/// The generated code for one struct or enum variant
+struct RouteImpl {
+    /// Expression constructing `Self` (or the variant) from `request`
+    parse: TokenStream2,
+    /// Pattern destructuring `self` (or the variant)
+    destructure: TokenStream2,
+    /// Statements building the path into `path` and the query into `query`
+    build: TokenStream2,
+}
+
+fn route_impl(
+    constructor: &TokenStream2,
+    pattern: &LitStr,
+    fields: &Fields,
+) -> syn::Result<RouteImpl> {
+    let segments = parse_pattern(pattern)?;
+
+    let named = match fields {
+        Fields::Named(named) => named.named.iter().collect::<Vec<_>>(),
+        Fields::Unit => Vec::new(),
+        Fields::Unnamed(_) => {
+            return Err(syn::Error::new(
+                fields.span(),
+                "Typed routes need named fields",
+            ));
+        }
+    };
+
+    let mut path_fields = Vec::new();
+    let mut field_inits = Vec::new();
+    let mut query_builders = Vec::new();
+    let mut bindings = Vec::new();
+
+    for field in &named {
+        let ident = field.ident.as_ref().expect("named field");
+        let name = ident.to_string().trim_start_matches("r#").to_string();
+        let binding = format_ident!("__{}", name);
+        bindings.push(quote!(#ident: #binding));
+
+        if is_query_field(&field.attrs)? {
+            if let Some(inner) = option_inner(&field.ty) {
+                field_inits.push(quote! {
+                    #ident: ::egui_router::typed::query_param::<#inner>(&request.query, #name)?
+                });
+                query_builders.push(quote! {
+                    if let Some(value) = #binding {
+                        query.push((#name, value.to_string()));
+                    }
+                });
+            } else {
+                let ty = &field.ty;
+                field_inits.push(quote! {
+                    #ident: ::egui_router::typed::query_param::<#ty>(&request.query, #name)?
+                        .ok_or_else(|| ::egui_router::HandlerError::Message(
+                            format!("Missing query param `{}`", #name),
+                        ))?
+                });
+                query_builders.push(quote! {
+                    query.push((#name, #binding.to_string()));
+                });
+            }
+        } else {
+            if !segments.iter().any(
+                |segment| matches!(segment, Segment::Param { name: param, .. } if *param == name),
+            ) {
+                return Err(syn::Error::new(
+                    ident.span(),
+                    format!(
+                        "`{name}` is not a param of route `{}`, add it to the route or mark it with `#[route(query)]`",
+                        pattern.value()
+                    ),
+                ));
+            }
+            let ty = &field.ty;
+            field_inits.push(quote! {
+                #ident: ::egui_router::typed::path_param::<#ty>(&request.params, #name)?
+            });
+            path_fields.push((name, binding));
+        }
+    }
+
+    let mut path_builders = Vec::new();
+    for segment in &segments {
+        match segment {
+            Segment::Literal(literal) => path_builders.push(quote!(path.push_str(#literal);)),
+            Segment::Param { name, catch_all } => {
+                let Some((_, binding)) = path_fields.iter().find(|(field, _)| field == name) else {
+                    return Err(syn::Error::new(
+                        pattern.span(),
+                        format!("Route param `{name}` has no matching field"),
+                    ));
+                };
+                path_builders.push(if *catch_all {
+                    quote!(::egui_router::typed::push_catch_all_param(&mut path, &#binding.to_string());)
+                } else {
+                    quote!(::egui_router::typed::push_path_param(&mut path, &#binding.to_string());)
+                });
+            }
+        }
+    }
+
+    let parse = if named.is_empty() {
+        quote!(#constructor)
+    } else {
+        quote!(#constructor { #(#field_inits),* })
+    };
+    let destructure = if named.is_empty() {
+        quote!(#constructor)
+    } else {
+        quote!(#constructor { #(#bindings),* })
+    };
+
+    Ok(RouteImpl {
+        parse,
+        destructure,
+        build: quote! {
+            #(#path_builders)*
+            #(#query_builders)*
+        },
+    })
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let mut patterns = Vec::new();
+    let mut routes = Vec::new();
+
+    match &input.data {
+        Data::Struct(data) => {
+            let pattern = route_pattern(&input.attrs, ident.span())?;
+            routes.push(route_impl(&quote!(Self), &pattern, &data.fields)?);
+            patterns.push(pattern);
+        }
+        Data::Enum(data) => {
+            for variant in &data.variants {
+                let variant_ident = &variant.ident;
+                let pattern = route_pattern(&variant.attrs, variant_ident.span())?;
+                routes.push(route_impl(
+                    &quote!(Self::#variant_ident),
+                    &pattern,
+                    &variant.fields,
+                )?);
+                patterns.push(pattern);
+            }
+        }
+        Data::Union(_) => {
+            return Err(syn::Error::new(
+                ident.span(),
+                "TypedRoute can't be derived for unions",
+            ));
+        }
+    }
+
+    let indices = 0..routes.len();
+    let parses = routes.iter().map(|route| &route.parse);
+    let destructures = routes.iter().map(|route| &route.destructure);
+    let builds = routes.iter().map(|route| &route.build);
+
+    Ok(quote! {
+        impl #impl_generics ::egui_router::typed::TypedRoute for #ident #ty_generics #where_clause {
+            const PATTERNS: &'static [&'static str] = &[#(#patterns),*];
+
+            #[allow(unused_variables)]
+            fn from_request<State>(
+                pattern_index: usize,
+                request: &::egui_router::Request<State>,
+            ) -> ::egui_router::HandlerResult<Self> {
+                match pattern_index {
+                    #(#indices => Ok(#parses),)*
+                    _ => Err(::egui_router::HandlerError::NotFound),
+                }
+            }
+
+            #[allow(unused_mut)]
+            fn to_path(&self) -> String {
+                let mut path = String::new();
+                let mut query: Vec<(&str, String)> = Vec::new();
+                match self {
+                    #(#destructures => { #builds })*
+                }
+                ::egui_router::typed::with_query(path, &query)
+            }
+        }
+
+        impl #impl_generics From<#ident #ty_generics> for String #where_clause {
+            fn from(route: #ident #ty_generics) -> String {
+                ::egui_router::typed::TypedRoute::to_path(&route)
+            }
+        }
+
+        impl #impl_generics From<&#ident #ty_generics> for String #where_clause {
+            fn from(route: &#ident #ty_generics) -> String {
+                ::egui_router::typed::TypedRoute::to_path(route)
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    fn segments(pattern: &str) -> syn::Result<Vec<Segment>> {
+        parse_pattern(&LitStr::new(pattern, proc_macro2::Span::call_site()))
+    }
+
+    fn param(name: &str, catch_all: bool) -> Segment {
+        Segment::Param {
+            name: name.to_string(),
+            catch_all,
+        }
+    }
+
+    #[test]
+    fn parse_pattern_splits_literals_and_params() {
+        assert_eq!(
+            segments("/post/{id}/comments/{comment}").unwrap(),
+            vec![
+                Segment::Literal("/post/".to_string()),
+                param("id", false),
+                Segment::Literal("/comments/".to_string()),
+                param("comment", false),
+            ]
+        );
+        assert_eq!(
+            segments("/files/{*path}").unwrap(),
+            vec![Segment::Literal("/files/".to_string()), param("path", true)]
+        );
+        assert_eq!(
+            segments("/{{escaped}}").unwrap(),
+            vec![Segment::Literal("/{escaped}".to_string())]
+        );
+    }
+
+    #[test]
+    fn parse_pattern_errors() {
+        assert_eq!(
+            segments("/post/{id").err().unwrap().to_string(),
+            "Unclosed `{` in route"
+        );
+        assert_eq!(
+            segments("/post/{}").err().unwrap().to_string(),
+            "Empty param name in route"
+        );
+        assert_eq!(
+            segments("/files/{*}").err().unwrap().to_string(),
+            "Empty param name in route"
+        );
+    }
+
+    #[test]
+    fn expand_enum() {
+        let tokens = expand(&parse_quote! {
+            enum AppRoute {
+                #[route("/")]
+                Home,
+                #[route("/files/{*path}")]
+                File { path: String },
+            }
+        })
+        .unwrap()
+        .to_string();
+        assert!(tokens.contains("push_catch_all_param"));
+        assert!(tokens.contains("\"/files/{*path}\""));
+    }
+}
@@ -0,0 +1,10 @@
+use egui_router::TypedRoute;
+
+#[derive(TypedRoute)]
+#[route("/post")]
+struct Post {
+    #[route(path)]
+    id: u32,
+}
+
+fn main() {}
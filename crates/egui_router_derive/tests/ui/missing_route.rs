@@ -0,0 +1,10 @@
+use egui_router::TypedRoute;
+
+#[derive(TypedRoute)]
+enum AppRoute {
+    #[route("/")]
+    Home,
+    Settings,
+}
+
+fn main() {}
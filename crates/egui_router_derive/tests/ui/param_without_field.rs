@@ -0,0 +1,7 @@
+use egui_router::TypedRoute;
+
+#[derive(TypedRoute)]
+#[route("/post/{id}")]
+struct Post {}
+
+fn main() {}
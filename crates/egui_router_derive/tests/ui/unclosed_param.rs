@@ -0,0 +1,9 @@
+use egui_router::TypedRoute;
+
+#[derive(TypedRoute)]
+#[route("/post/{id")]
+struct Post {
+    id: u32,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use egui_router::TypedRoute;
+
+#[derive(TypedRoute)]
+#[route("/post")]
+union Post {
+    id: u32,
+}
+
+fn main() {}
@@ -272,6 +272,14 @@ impl<T: MaybeSend + MaybeSync + 'static, E: Display + Debug + MaybeSend + MaybeS
         }
     }
 
+    /// Check whether the data has arrived without showing the ui.
+    /// Useful to wait for the data before showing the suspense.
+    pub fn poll(&mut self, ctx: &egui::Context) {
+        if let Some(result) = self.inbox.read(ctx).last() {
+            self.data = Some(result);
+        }
+    }
+
     /// Returns true if the data is loading.
     pub fn loading(&self) -> bool {
         self.data.is_none()